@@ -0,0 +1,252 @@
+//! Step-by-step animations of a solver at work, for `--animate`.
+//!
+//! A solver that supports it sends a [`Picture`] to a [`Screen`] after every
+//! step of its simulation or search. The [`Terminal`] redraws each frame in
+//! place on stderr with ANSI escape codes at a fixed frame rate, so stdout
+//! keeps only the answers, and reads keys from stdin to pause, step and quit; a [`FrameDump`] writes the frames to a
+//! directory of text files instead.
+
+use std::fs;
+use std::io::{self, IsTerminal, Read, Write};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::render::Picture;
+
+/// How often the terminal checks for keys while it waits.
+const POLL: Duration = Duration::from_millis(10);
+
+/// Where the frames of an animation go.
+pub trait Screen {
+    /// Shows `frame`, described by `caption`. Returns [`ControlFlow::Break`]
+    /// when the viewer has had enough, and the solver should stop.
+    fn show(&mut self, frame: &Picture, caption: &str) -> ControlFlow<()>;
+}
+
+/// What a key pressed during an animation does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    /// Stops or restarts the animation.
+    Pause,
+    /// Pauses the animation and shows the next frame.
+    Step,
+    Quit,
+}
+
+impl Key {
+    pub fn from_byte(byte: u8) -> Option<Key> {
+        match byte {
+            b' ' | b'p' => Some(Key::Pause),
+            b'n' | b's' => Some(Key::Step),
+            // Ctrl-C does not interrupt while the terminal is raw
+            b'q' | b'\x1b' | b'\x03' => Some(Key::Quit),
+            _ => None,
+        }
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// The terminal's settings while keys are read one at a time, without echo
+// and without blocking. The old settings come back when this is dropped.
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    fn enter() -> Option<RawMode> {
+        if !io::stdin().is_terminal() {
+            return None;
+        }
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "0", "time", "0"])?;
+        Some(RawMode { saved })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[&self.saved]);
+    }
+}
+
+/// Plays an animation in the terminal, redrawing every frame in place on
+/// stderr.
+///
+/// Space pauses and resumes, `n` steps one frame at a time and `q` quits.
+/// Keys only work when stdin is a terminal; otherwise the animation just
+/// plays through.
+pub struct Terminal {
+    delay: Duration,
+    paused: bool,
+    frames: usize,
+    raw: Option<RawMode>,
+}
+
+impl Terminal {
+    /// Takes over the terminal to show `fps` frames a second.
+    pub fn new(fps: u32) -> Terminal {
+        // clear the screen and hide the cursor
+        eprint!("\x1b[2J\x1b[?25l");
+        Terminal {
+            delay: Duration::from_secs(1) / fps.max(1),
+            paused: false,
+            frames: 0,
+            raw: RawMode::enter(),
+        }
+    }
+
+    fn key(&self) -> Option<Key> {
+        self.raw.as_ref()?;
+        let mut byte = [0];
+        match io::stdin().read(&mut byte) {
+            Ok(1) => Key::from_byte(byte[0]),
+            _ => None,
+        }
+    }
+}
+
+impl Screen for Terminal {
+    fn show(&mut self, frame: &Picture, caption: &str) -> ControlFlow<()> {
+        self.frames += 1;
+        let state = if self.paused { "paused" } else { "playing" };
+        let help = match self.raw {
+            Some(_) => "  [space] pause  [n] step  [q] quit",
+            None => "",
+        };
+        // stderr is unbuffered, so the frame is written in one go to avoid flicker
+        let text = format!(
+            "\x1b[H{}frame {}: {} ({}){}\x1b[K\n",
+            frame.to_ansi(),
+            self.frames,
+            caption,
+            state,
+            help
+        );
+        let _ = io::stderr().lock().write_all(text.as_bytes());
+
+        let shown = Instant::now();
+        loop {
+            match self.key() {
+                Some(Key::Quit) => return ControlFlow::Break(()),
+                Some(Key::Step) => {
+                    self.paused = true;
+                    return ControlFlow::Continue(());
+                }
+                Some(Key::Pause) => self.paused = !self.paused,
+                None => {}
+            }
+            let remaining = self.delay.saturating_sub(shown.elapsed());
+            if !self.paused && remaining.is_zero() {
+                return ControlFlow::Continue(());
+            }
+            let wait = if self.paused {
+                POLL
+            } else {
+                remaining.min(POLL)
+            };
+            thread::sleep(wait);
+        }
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        // show the cursor again
+        eprint!("\x1b[?25h");
+    }
+}
+
+/// Writes every frame to its own text file, `frame00001.txt` onwards, with
+/// the caption on the first line and the frame's text below.
+pub struct FrameDump {
+    dir: PathBuf,
+    frames: usize,
+    error: Option<String>,
+}
+
+impl FrameDump {
+    /// Dumps frames into `dir`, which is created if need be.
+    pub fn new(dir: &Path) -> Result<FrameDump, String> {
+        fs::create_dir_all(dir)
+            .map_err(|err| format!("cannot create `{}`: {}", dir.display(), err))?;
+        Ok(FrameDump {
+            dir: dir.to_path_buf(),
+            frames: 0,
+            error: None,
+        })
+    }
+
+    /// The number of frames written, or why writing one failed.
+    pub fn finish(self) -> Result<usize, String> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.frames),
+        }
+    }
+}
+
+impl Screen for FrameDump {
+    fn show(&mut self, frame: &Picture, caption: &str) -> ControlFlow<()> {
+        self.frames += 1;
+        let path = self.dir.join(format!("frame{:05}.txt", self.frames));
+        let text = format!("{}\n{}", caption, frame.text());
+        match fs::write(&path, text) {
+            Ok(()) => ControlFlow::Continue(()),
+            Err(err) => {
+                self.error = Some(format!("cannot write `{}`: {}", path.display(), err));
+                ControlFlow::Break(())
+            }
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::{Class, Colour};
+    use crate::Grid;
+
+    #[test]
+    fn test_key() {
+        assert_eq!(Key::from_byte(b' '), Some(Key::Pause));
+        assert_eq!(Key::from_byte(b'n'), Some(Key::Step));
+        assert_eq!(Key::from_byte(b'q'), Some(Key::Quit));
+        assert_eq!(Key::from_byte(b'\x03'), Some(Key::Quit));
+        assert_eq!(Key::from_byte(b'x'), None);
+    }
+
+    #[test]
+    fn test_frame_dump() {
+        let dir = std::env::temp_dir().join(format!("aoc_frames_{}", std::process::id()));
+        let class = Class::new("cell", Colour::rgb(0, 0, 0));
+        let mut dump = FrameDump::new(&dir).unwrap();
+        for c in ['a', 'b'] {
+            let frame = Picture::classify(Grid::new(2, 1, c), |_, _| class);
+            assert_eq!(
+                dump.show(&frame, &format!("step {}", c)),
+                ControlFlow::Continue(())
+            );
+        }
+        assert_eq!(dump.finish(), Ok(2));
+        assert_eq!(
+            fs::read_to_string(dir.join("frame00002.txt")).unwrap(),
+            "step b\nbb\n"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,86 @@
+use std::fmt;
+
+/// A puzzle answer, wide enough for any of the integer types the days return.
+/// `None` is the answer to an input that has no solution, such as a network
+/// without the node a walk starts from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    None,
+    Signed(i64),
+    Unsigned(u64),
+    Big(i128),
+    BigUnsigned(u128),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::None => write!(f, "none"),
+            Answer::Signed(n) => write!(f, "{}", n),
+            Answer::Unsigned(n) => write!(f, "{}", n),
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::BigUnsigned(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($variant:ident as $target:ty: $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::$variant(n as $target)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(Signed as i64: i8, i16, i32, i64, isize);
+impl_from!(Unsigned as u64: u8, u16, u32, u64, usize);
+impl_from!(Big as i128: i128);
+impl_from!(BigUnsigned as u128: u128);
+
+impl<T: Into<Answer>> From<Option<T>> for Answer {
+    fn from(answer: Option<T>) -> Self {
+        answer.map_or(Answer::None, Into::into)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from() {
+        assert_eq!(Answer::from(-3i32), Answer::Signed(-3));
+        assert_eq!(Answer::from(7usize), Answer::Unsigned(7));
+        assert_eq!(Answer::from(1i128 << 100), Answer::Big(1 << 100));
+        assert_eq!(Answer::from(u128::MAX), Answer::BigUnsigned(u128::MAX));
+        assert_eq!(Answer::from(Some(7usize)), Answer::Unsigned(7));
+        assert_eq!(Answer::from(None::<usize>), Answer::None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::None.to_string(), "none");
+        assert_eq!(Answer::Signed(-42).to_string(), "-42");
+        assert_eq!(
+            Answer::Unsigned(u64::MAX).to_string(),
+            "18446744073709551615"
+        );
+        assert_eq!(
+            Answer::Big(i128::MIN).to_string(),
+            "-170141183460469231731687303715884105728"
+        );
+        assert_eq!(
+            Answer::BigUnsigned(i128::MAX as u128 + 1).to_string(),
+            "170141183460469231731687303715884105728"
+        );
+        assert_eq!(
+            Answer::BigUnsigned(u128::MAX).to_string(),
+            "340282366920938463463374607431768211455"
+        );
+    }
+}
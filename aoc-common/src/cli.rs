@@ -0,0 +1,104 @@
+use std::collections::{HashMap, HashSet};
+
+/// Command-line arguments split into positionals, boolean flags and options
+/// that take a value (`--name value` or `--name=value`). Flags may also be
+/// short ones such as `-v`.
+#[derive(Debug, Default)]
+pub struct Args {
+    positional: Vec<String>,
+    flags: HashSet<String>,
+    options: HashMap<String, String>,
+}
+
+impl Args {
+    pub fn parse<I>(args: I, flags: &[&str], options: &[&str]) -> Result<Args, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut parsed: Args = Default::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            if flags.contains(&arg.as_str()) {
+                parsed.flags.insert(arg);
+                continue;
+            }
+            if !arg.starts_with("--") {
+                // `-` is a positional too: it stands for stdin
+                parsed.positional.push(arg);
+                continue;
+            }
+
+            let (name, inline_value) = match arg.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+
+            if flags.contains(&name.as_str()) && inline_value.is_none() {
+                parsed.flags.insert(name);
+            } else if options.contains(&name.as_str()) {
+                let value = match inline_value {
+                    Some(value) => value,
+                    None => args
+                        .next()
+                        .ok_or_else(|| format!("option `{}` needs a value", name))?,
+                };
+                parsed.options.insert(name, value);
+            } else {
+                return Err(format!("unknown option `{}`", name));
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    pub fn positional(&self) -> &[String] {
+        &self.positional
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.contains(name)
+    }
+
+    pub fn option(&self, name: &str) -> Option<&str> {
+        self.options.get(name).map(|v| v.as_str())
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Args, String> {
+        Args::parse(
+            s.split_whitespace().map(|a| a.to_string()),
+            &["--example", "-v"],
+            &["--part"],
+        )
+    }
+
+    #[test]
+    fn test_parse() {
+        let args = parse("in.txt --part 2 --example").unwrap();
+        assert_eq!(args.positional(), ["in.txt"]);
+        assert_eq!(args.option("--part"), Some("2"));
+        assert!(args.flag("--example"));
+
+        let args = parse("- --part=both").unwrap();
+        assert_eq!(args.positional(), ["-"]);
+        assert_eq!(args.option("--part"), Some("both"));
+        assert!(!args.flag("--example"));
+
+        let args = parse("-v in.txt -x").unwrap();
+        assert_eq!(args.positional(), ["in.txt", "-x"]);
+        assert!(args.flag("-v"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("--bogus").is_err());
+        assert!(parse("--part").is_err());
+        assert!(parse("--example=yes").is_err());
+    }
+}
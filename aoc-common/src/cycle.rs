@@ -0,0 +1,245 @@
+//! Cycle detection for sequences `x0, f(x0), f(f(x0)), ...` produced by
+//! repeatedly applying a step function to a state.
+//!
+//! [`floyd`] and [`brent`] keep only a couple of states in memory;
+//! [`hashed`] remembers every state, which makes looking any of them up again
+//! free.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The shape of an eventually repeating sequence: the states at steps
+/// `prefix..prefix + length` repeat forever after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step whose state equals the state at step `n`.
+    pub fn reduce(&self, n: u128) -> usize {
+        match n.checked_sub(self.prefix as u128) {
+            Some(into_cycle) => self.prefix + (into_cycle % self.length as u128) as usize,
+            None => n as usize,
+        }
+    }
+}
+
+/// Floyd's tortoise and hare.
+pub fn floyd<S, F>(start: S, mut f: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let mut tortoise = f(&start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        hare = f(&hare);
+    }
+
+    // the hare is now a multiple of the cycle length ahead of the tortoise
+    let mut prefix = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+
+    let mut length = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+    Cycle { prefix, length }
+}
+
+/// Brent's algorithm, which usually needs fewer steps than Floyd's.
+pub fn brent<S, F>(start: S, mut f: F) -> Cycle
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // the tortoise teleports to the hare at every power of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    let mut prefix = 0;
+    tortoise = start.clone();
+    hare = start;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        prefix += 1;
+    }
+    Cycle { prefix, length }
+}
+
+/// The state at step `n`, however large: only the prefix and a few rounds of
+/// the cycle are ever stepped through.
+pub fn fast_forward<S, F>(start: S, mut f: F, n: u128) -> S
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    let cycle = brent(start.clone(), &mut f);
+    (0..cycle.reduce(n)).fold(start, |state, _| f(&state))
+}
+
+/// Every state of a sequence seen so far, in order.
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    seen: HashMap<S, usize>,
+    states: Vec<S>,
+}
+
+impl<S: Clone + Eq + Hash> Default for History<S> {
+    fn default() -> Self {
+        History {
+            seen: HashMap::new(),
+            states: Vec::new(),
+        }
+    }
+}
+
+impl<S: Clone + Eq + Hash> History<S> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Records the state at the next step, returning the cycle instead once
+    /// a state repeats.
+    pub fn push(&mut self, state: S) -> Option<Cycle> {
+        if let Some(&prefix) = self.seen.get(&state) {
+            return Some(Cycle {
+                prefix,
+                length: self.states.len() - prefix,
+            });
+        }
+        self.seen.insert(state.clone(), self.states.len());
+        self.states.push(state);
+        None
+    }
+
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// The state at step `n` of the sequence, given the `cycle` this
+    /// history found.
+    pub fn nth(&self, cycle: &Cycle, n: u128) -> &S {
+        &self.states[cycle.reduce(n)]
+    }
+}
+
+/// Detects the cycle by remembering every state until one repeats.
+pub fn hashed<S, F>(start: S, mut f: F) -> (Cycle, History<S>)
+where
+    S: Clone + Eq + Hash,
+    F: FnMut(&S) -> S,
+{
+    let mut history = History::new();
+    let mut state = start;
+    loop {
+        let next = f(&state);
+        if let Some(cycle) = history.push(state) {
+            return (cycle, history);
+        }
+        state = next;
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0, 1, 2, ..., 6, then 3, 4, 5, 6 forever
+    fn step(n: &u32) -> u32 {
+        if *n == 6 {
+            3
+        } else {
+            n + 1
+        }
+    }
+
+    const CYCLE: Cycle = Cycle {
+        prefix: 3,
+        length: 4,
+    };
+
+    #[test]
+    fn test_detectors_agree() {
+        assert_eq!(floyd(0, step), CYCLE);
+        assert_eq!(brent(0, step), CYCLE);
+        assert_eq!(hashed(0, step).0, CYCLE);
+
+        // a sequence that is a cycle from the start
+        let pure = Cycle {
+            prefix: 0,
+            length: 5,
+        };
+        let f = |n: &u32| (n + 1) % 5;
+        assert_eq!(floyd(0, f), pure);
+        assert_eq!(brent(0, f), pure);
+        assert_eq!(hashed(0, f).0, pure);
+
+        // and one that gets stuck on its first state
+        assert_eq!(brent(7, |n: &u32| *n).length, 1);
+        assert_eq!(floyd(7, |n: &u32| *n).prefix, 0);
+    }
+
+    #[test]
+    fn test_reduce() {
+        assert_eq!(CYCLE.reduce(2), 2);
+        assert_eq!(CYCLE.reduce(3), 3);
+        assert_eq!(CYCLE.reduce(7), 3);
+        assert_eq!(CYCLE.reduce(10), 6);
+        assert_eq!(CYCLE.reduce(u128::MAX), 3 + ((u128::MAX - 3) % 4) as usize);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        for n in 0..20 {
+            let expected = (0..n).fold(0, |s, _| step(&s));
+            assert_eq!(fast_forward(0, step, n), expected);
+        }
+        assert_eq!(fast_forward(0, step, 1_000_000_000_000_000_000_000), 4);
+    }
+
+    #[test]
+    fn test_history() {
+        let (cycle, history) = hashed(0, step);
+        assert_eq!(history.states(), [0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(*history.nth(&cycle, 1_000_000_000), 4);
+
+        let mut history = History::new();
+        assert_eq!(history.push('a'), None);
+        assert_eq!(history.push('b'), None);
+        assert_eq!(
+            history.push('b'),
+            Some(Cycle {
+                prefix: 1,
+                length: 1
+            })
+        );
+    }
+}
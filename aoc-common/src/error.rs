@@ -0,0 +1,56 @@
+use std::error::Error;
+use std::fmt;
+
+/// Raised by [`Solution::parse`](crate::Solution::parse) when the input does
+/// not have the expected shape. Lines and columns are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new<E, F>(day: u8, line: usize, column: usize, expected: E, found: F) -> Self
+    where
+        E: Into<String>,
+        F: Into<String>,
+    {
+        ParseError {
+            day,
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: expected {}, found {}",
+            self.day, self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let err = ParseError::new(4, 3, 9, "a number", "`x`");
+        assert_eq!(
+            err.to_string(),
+            "day 4, line 3, column 9: expected a number, found `x`"
+        );
+    }
+}
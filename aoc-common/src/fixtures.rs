@@ -0,0 +1,212 @@
+//! Example-driven tests read from a day's `fixtures/` directory.
+//!
+//! Every fixture is a pair of files: `<name>.txt` holds the puzzle input and
+//! `<name>.answers` the expected answers, one `part1: <answer>` or
+//! `part2: <answer>` line per part that the fixture covers, plus any `#`
+//! comment lines. A day's build script calls [`generate_tests`], and its test
+//! module calls [`fixture_tests!`](crate::fixture_tests) to get one test per
+//! fixture and part.
+//!
+//! The generated tests also [fuzz](crate::fuzz) the day's parser and replay
+//! the inputs in `fixtures/fuzz/` that once made it panic.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::{Answer, Solution};
+
+/// A fixture's expected answers, as written in its `.answers` file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut expected = Expected::default();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let unknown = || format!("line {}: expected `part1:` or `part2:`", i + 1);
+            let (key, answer) = line.split_once(':').ok_or_else(unknown)?;
+            let slot = match key {
+                "part1" => &mut expected.part1,
+                "part2" => &mut expected.part2,
+                _ => return Err(unknown()),
+            };
+            let answer = answer.trim();
+            if answer.is_empty() {
+                return Err(format!("line {}: missing answer", i + 1));
+            }
+            *slot = Some(answer.to_string());
+        }
+        Ok(expected)
+    }
+
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// The names of the fixtures in `dir`, sorted, or none if it does not exist.
+pub fn names(dir: &Path) -> Vec<String> {
+    stems(dir, "answers")
+}
+
+// the names of the files in `dir` with the given extension, sorted
+fn stems(dir: &Path, extension: &str) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            (path.extension()? == extension)
+                .then(|| path.file_stem()?.to_str().map(String::from))?
+        })
+        .collect();
+    names.sort();
+    names
+}
+
+fn read(dir: &Path, name: &str, extension: &str) -> String {
+    let path = dir.join(format!("{}.{}", name, extension));
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+/// Reads the expected answers of fixture `name` in `dir`.
+pub fn expected(dir: &Path, name: &str) -> Expected {
+    Expected::parse(&read(dir, name, "answers"))
+        .unwrap_or_else(|e| panic!("{}/{}.answers: {}", dir.display(), name, e))
+}
+
+fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn test_name(name: &str, part: u8) -> String {
+    format!("fixture_{}_part{}", identifier(name), part)
+}
+
+/// Writes the tests for the fixtures in the crate's `fixtures/` directory to
+/// `$OUT_DIR/fixture_tests.rs`. Meant to be called from a build script.
+pub fn generate_tests() {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("run from a build script");
+    let out_dir = std::env::var("OUT_DIR").expect("run from a build script");
+    let dir = Path::new(&manifest_dir).join("fixtures");
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut tests = String::new();
+    for name in names(&dir) {
+        println!(
+            "cargo:rerun-if-changed={}",
+            dir.join(format!("{}.answers", name)).display()
+        );
+        let expected = expected(&dir, &name);
+        for part in [1, 2]
+            .into_iter()
+            .filter(|&part| expected.part(part).is_some())
+        {
+            writeln!(
+                tests,
+                "#[test]\nfn {}() {{\n    aoc_common::fixtures::check::<FixtureDay>(\
+                 std::path::Path::new(env!(\"CARGO_MANIFEST_DIR\")), {:?}, {});\n}}",
+                test_name(&name, part),
+                name,
+                part
+            )
+            .unwrap();
+        }
+    }
+
+    tests += "#[test]\nfn fuzz_parse() {\n    aoc_common::fuzz::parse::<FixtureDay>(\
+              std::path::Path::new(env!(\"CARGO_MANIFEST_DIR\")));\n}\n";
+    for name in stems(&dir.join("fuzz"), "txt") {
+        writeln!(
+            tests,
+            "#[test]\nfn fuzz_{}() {{\n    aoc_common::fuzz::replay::<FixtureDay>(\
+             std::path::Path::new(env!(\"CARGO_MANIFEST_DIR\")), {:?});\n}}",
+            identifier(&name),
+            name
+        )
+        .unwrap();
+    }
+    fs::write(Path::new(&out_dir).join("fixture_tests.rs"), tests).expect("cannot write tests");
+}
+
+/// Runs part `part` of `S` on fixture `name` and checks the answer.
+pub fn check<S: Solution>(crate_dir: &Path, name: &str, part: u8) {
+    let dir = crate_dir.join("fixtures");
+    let input = read(&dir, name, "txt");
+    let expected = expected(&dir, name);
+    let expected = expected
+        .part(part)
+        .expect("the fixture has an answer for the part");
+
+    let parsed = S::parse(&input).unwrap_or_else(|e| panic!("fixture `{}`: {}", name, e));
+    let answer: Answer = if part == 1 {
+        S::part1(&parsed).into()
+    } else {
+        S::part2(&parsed).into()
+    };
+    assert_eq!(
+        answer.to_string(),
+        expected,
+        "fixture `{}`, part {}",
+        name,
+        part
+    );
+}
+
+/// Expands to one test per fixture and part of the calling crate, as
+/// written by [`generate_tests`] in its build script.
+#[macro_export]
+macro_rules! fixture_tests {
+    ($day:ty) => {
+        type FixtureDay = $day;
+        include!(concat!(env!("OUT_DIR"), "/fixture_tests.rs"));
+    };
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expected() {
+        let expected = Expected::parse("part1: 35\n\npart2:  46 \n").unwrap();
+        assert_eq!(expected.part(1), Some("35"));
+        assert_eq!(expected.part(2), Some("46"));
+
+        let expected = Expected::parse("# only the second part\npart2: 6\n").unwrap();
+        assert_eq!(expected.part(1), None);
+
+        assert!(Expected::parse("part3: 1\n").is_err());
+        assert!(Expected::parse("part1:\n").is_err());
+    }
+
+    #[test]
+    fn test_test_name() {
+        assert_eq!(test_name("example", 1), "fixture_example_part1");
+        assert_eq!(
+            test_name("Leading-newline 2", 2),
+            "fixture_leading_newline_2_part2"
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! A small mutation fuzzer for the parsers that runs under `cargo test`.
+//!
+//! Inputs start out as a day's worked example or a generated input and get a
+//! few random edits: spans deleted, duplicated or replaced with awkward
+//! tokens, lines dropped or repeated, the end cut off. Parsing such an input
+//! may fail but must never panic. A panicking input is shrunk and saved to
+//! the day's `fixtures/fuzz/` directory, and the tests written by
+//! [`fixtures::generate_tests`](crate::fixtures::generate_tests) replay every
+//! saved input from then on.
+
+use std::fs;
+use std::path::Path;
+
+use crate::{input_hash, property, Rng, Solution};
+
+/// How many mutated inputs every parser is fed.
+pub const CASES: usize = 500;
+
+// text that parsers tend to trip over
+const TOKENS: &[&str] = &[
+    "",
+    "0",
+    "-1",
+    "99999999999999999999",
+    "18446744073709551616",
+    " ",
+    "  ",
+    "\n",
+    "\n\n",
+    "\r\n",
+    "\t",
+    ":",
+    ",",
+    "=",
+    "-",
+    "é",
+];
+
+/// Makes a few random edits to `input`.
+pub fn mutate(rng: &mut Rng, input: &str) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for _ in 0..rng.range(1..4) {
+        let at = rng.index(0..chars.len() + 1);
+        let end = (at + rng.index(0..9)).min(chars.len());
+        match rng.below(6) {
+            0 => {
+                chars.drain(at..end);
+            }
+            1 => {
+                let span = chars[at..end].to_vec();
+                chars.splice(at..at, span);
+            }
+            2 => {
+                chars.splice(at..end, rng.choose(TOKENS).chars());
+            }
+            3 if !chars.is_empty() => {
+                let c = *rng.choose(&chars);
+                chars.insert(at, c);
+            }
+            4 => chars.truncate(at),
+            _ => {
+                let text: String = chars.iter().collect();
+                let mut lines: Vec<&str> = text.split('\n').collect();
+                let i = rng.index(0..lines.len());
+                if rng.chance(1, 2) {
+                    lines.remove(i);
+                } else {
+                    lines.insert(i, lines[i]);
+                }
+                chars = lines.join("\n").chars().collect();
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+// whole lines first, then single characters
+fn shrink_input(input: &str) -> Vec<String> {
+    let lines: Vec<&str> = input.split('\n').collect();
+    let chars: Vec<char> = input.chars().collect();
+    property::shrink_vec(&lines, |_| Vec::new())
+        .into_iter()
+        .map(|lines| lines.join("\n"))
+        .chain(
+            property::shrink_vec(&chars, |_| Vec::new())
+                .into_iter()
+                .map(|chars| chars.into_iter().collect()),
+        )
+        .collect()
+}
+
+/// Feeds mutated inputs to `S::parse`. If one makes it panic, saves the
+/// shrunk input to `crate_dir`'s `fixtures/fuzz/` directory and panics.
+pub fn parse<S: Solution>(crate_dir: &Path) {
+    let originals: Vec<String> = [S::EXAMPLE.to_string()]
+        .into_iter()
+        .chain((1..4).map(|size| S::generate(&mut Rng::new(size), size as usize)))
+        .collect();
+    let failure = property::find_failure(
+        CASES,
+        |rng| {
+            let original = rng.choose(&originals);
+            mutate(rng, original)
+        },
+        |input| shrink_input(input),
+        |input| {
+            let _ = S::parse(input);
+            Ok(())
+        },
+    );
+
+    if let Some(failure) = failure {
+        let dir = crate_dir.join("fixtures").join("fuzz");
+        let path = dir.join(format!("{}.txt", input_hash(&failure.input)));
+        let saved = fs::create_dir_all(&dir).and_then(|_| fs::write(&path, &failure.input));
+        match saved {
+            Ok(()) => panic!("{}\nsaved to {}", failure, path.display()),
+            Err(err) => panic!("{}\ncannot save to {}: {}", failure, path.display(), err),
+        }
+    }
+}
+
+/// Parses the saved input `name` from `crate_dir`'s `fixtures/fuzz/`
+/// directory, which must not panic.
+pub fn replay<S: Solution>(crate_dir: &Path, name: &str) {
+    let path = crate_dir
+        .join("fixtures")
+        .join("fuzz")
+        .join(format!("{}.txt", name));
+    let input = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    let _ = S::parse(&input);
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mutate() {
+        let input = "Time: 7 15 30\nDistance: 9 40 200\n";
+        let mutated: Vec<String> = (0..100)
+            .map(|seed| mutate(&mut Rng::new(seed), input))
+            .collect();
+        assert_eq!(mutated[0], mutate(&mut Rng::new(0), input));
+        assert!(mutated.iter().filter(|m| *m != input).count() > 80);
+        assert!(mutated.iter().any(|m| m.lines().count() < 2));
+        assert!(mutated.iter().any(|m| m.len() > input.len()));
+    }
+
+    #[test]
+    fn test_shrink_input() {
+        let candidates = shrink_input("ab\ncd");
+        assert_eq!(
+            &candidates[..4],
+            [
+                "ab".to_string(),
+                "cd".to_string(),
+                "cd".to_string(),
+                "ab".to_string()
+            ]
+        );
+        assert!(candidates.contains(&"a\ncd".to_string()));
+        assert!(candidates.iter().all(|c| c.len() < 5));
+    }
+}
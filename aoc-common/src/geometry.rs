@@ -0,0 +1,181 @@
+use std::fmt;
+
+/// A compass direction on a grid whose rows grow southwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    N,
+    E,
+    S,
+    W,
+}
+
+impl Direction {
+    /// All four directions, clockwise from north.
+    pub const ALL: [Direction; 4] = [Direction::N, Direction::E, Direction::S, Direction::W];
+
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Direction::N => Direction::W,
+            Direction::E => Direction::N,
+            Direction::S => Direction::E,
+            Direction::W => Direction::S,
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::N => Direction::E,
+            Direction::E => Direction::S,
+            Direction::S => Direction::W,
+            Direction::W => Direction::N,
+        }
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Direction::N => Direction::S,
+            Direction::E => Direction::W,
+            Direction::S => Direction::N,
+            Direction::W => Direction::E,
+        }
+    }
+
+    /// The `(row, column)` offset of one step in this direction.
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::N => (-1, 0),
+            Direction::E => (0, 1),
+            Direction::S => (1, 0),
+            Direction::W => (0, -1),
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::E | Direction::W)
+    }
+
+    /// The direction a beam leaves a `/` or `\` mirror in; any other tile
+    /// lets it pass straight through.
+    pub fn reflect(self, mirror: char) -> Direction {
+        match (mirror, self.is_horizontal()) {
+            ('/', true) | ('\\', false) => self.turn_left(),
+            ('/', false) | ('\\', true) => self.turn_right(),
+            _ => self,
+        }
+    }
+
+    /// The two directions a beam splits into when it hits the flat side of a
+    /// `|` or `-` splitter, or `None` if it passes through the tile.
+    pub fn split(self, splitter: char) -> Option<[Direction; 2]> {
+        match (splitter, self.is_horizontal()) {
+            ('|', true) | ('-', false) => Some([self.turn_left(), self.turn_right()]),
+            _ => None,
+        }
+    }
+}
+
+/// A `(row, column)` position on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Point {
+    pub const fn new(row: usize, col: usize) -> Point {
+        Point { row, col }
+    }
+
+    /// The point `delta` away, or `None` if that has a negative coordinate
+    /// or overflows.
+    pub fn checked_add(self, (drow, dcol): (isize, isize)) -> Option<Point> {
+        Some(Point {
+            row: self.row.checked_add_signed(drow)?,
+            col: self.col.checked_add_signed(dcol)?,
+        })
+    }
+
+    /// The point `n` steps away in direction `dir`.
+    pub fn checked_step(self, dir: Direction, n: usize) -> Option<Point> {
+        let n = isize::try_from(n).ok()?;
+        let (drow, dcol) = dir.delta();
+        self.checked_add((drow.checked_mul(n)?, dcol.checked_mul(n)?))
+    }
+
+    pub fn manhattan(self, other: Point) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((row, col): (usize, usize)) -> Point {
+        Point { row, col }
+    }
+}
+
+impl From<Point> for (usize, usize) {
+    fn from(point: Point) -> (usize, usize) {
+        (point.row, point.col)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.row, self.col)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        for dir in Direction::ALL {
+            assert_eq!(dir.turn_left().turn_right(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.reverse());
+            let (drow, dcol) = dir.delta();
+            assert_eq!(dir.reverse().delta(), (-drow, -dcol));
+        }
+        assert_eq!(Direction::N.turn_right(), Direction::E);
+        assert_eq!(Direction::N.turn_left(), Direction::W);
+    }
+
+    #[test]
+    fn test_reflect() {
+        use Direction::*;
+        let slash: Vec<_> = [N, E, S, W].map(|d| d.reflect('/')).to_vec();
+        assert_eq!(slash, [E, N, W, S]);
+        let backslash: Vec<_> = [N, E, S, W].map(|d| d.reflect('\\')).to_vec();
+        assert_eq!(backslash, [W, S, E, N]);
+        assert_eq!(E.reflect('.'), E);
+    }
+
+    #[test]
+    fn test_split() {
+        use Direction::*;
+        assert_eq!(E.split('|'), Some([N, S]));
+        assert_eq!(N.split('-'), Some([W, E]));
+        assert_eq!(N.split('|'), None);
+        assert_eq!(W.split('-'), None);
+        assert_eq!(W.split('.'), None);
+    }
+
+    #[test]
+    fn test_point_arithmetic() {
+        let p = Point::new(1, 2);
+        assert_eq!(p.checked_add((-1, 3)), Some(Point::new(0, 5)));
+        assert_eq!(p.checked_add((-2, 0)), None);
+        assert_eq!(p.checked_step(Direction::W, 2), Some(Point::new(1, 0)));
+        assert_eq!(p.checked_step(Direction::W, 3), None);
+        assert_eq!(p.checked_step(Direction::S, usize::MAX), None);
+        assert_eq!(
+            Point::new(usize::MAX, 0).checked_step(Direction::S, 1),
+            None
+        );
+        assert_eq!(p.manhattan(Point::new(4, 0)), 5);
+        assert_eq!(<(usize, usize)>::from(p), (1, 2));
+        assert_eq!(p.to_string(), "(1, 2)");
+    }
+}
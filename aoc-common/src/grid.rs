@@ -0,0 +1,335 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::{parse, Direction, ParseError, Point};
+
+/// A rectangular 2-D array stored row by row. Positions are `(row, column)`
+/// pairs, and every accessor is bounds-checked.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+const NEIGHBOURS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "all rows of a grid must have the same length"
+        );
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, col): (usize, usize)) -> bool {
+        row < self.height && col < self.width
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// The position `delta` away from `pos`, if it is inside the grid.
+    pub fn offset(
+        &self,
+        (row, col): (usize, usize),
+        delta: (isize, isize),
+    ) -> Option<(usize, usize)> {
+        let pos = (
+            row.checked_add_signed(delta.0)?,
+            col.checked_add_signed(delta.1)?,
+        );
+        self.contains(pos).then_some(pos)
+    }
+
+    /// The neighbour of `pos` in direction `dir`, if it is inside the grid.
+    pub fn step(&self, pos: Point, dir: Direction) -> Option<Point> {
+        self.offset(pos.into(), dir.delta()).map(Point::from)
+    }
+
+    /// The up to 4 orthogonally adjacent positions inside the grid.
+    pub fn neighbours4(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS4
+            .iter()
+            .filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    /// The up to 8 positions inside the grid that touch `pos`, diagonals included.
+    pub fn neighbours8(&self, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        NEIGHBOURS8
+            .iter()
+            .filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero size, and an empty grid has no rows anyway
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        assert!(col < self.width, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    /// Every position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell together with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell, row by row, that satisfies `pred`.
+    pub fn find<P>(&self, mut pred: P) -> Option<(usize, usize)>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter().find(|(_, cell)| pred(cell)).map(|(pos, _)| pos)
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    fn remap<F>(&self, width: usize, height: usize, source: F) -> Grid<T>
+    where
+        T: Clone,
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let cells = (0..height)
+            .flat_map(|row| (0..width).map(move |col| (row, col)))
+            .map(|(row, col)| self[source(row, col)].clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.remap(self.height, self.width, |row, col| (col, row))
+    }
+
+    /// Rotates the grid a quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let height = self.height;
+        self.remap(self.height, self.width, |row, col| (height - 1 - col, row))
+    }
+
+    /// Rotates the grid a quarter turn counter-clockwise.
+    pub fn rotate_ccw(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let width = self.width;
+        self.remap(self.height, self.width, |row, col| (col, width - 1 - row))
+    }
+
+    /// Parses a rectangular grid of `allowed` characters (see
+    /// [`parse::grid`]), converting every character with `f`.
+    pub fn parse_with<F>(day: u8, input: &str, allowed: &str, mut f: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> T,
+    {
+        let rows = parse::grid(day, input, allowed)?;
+        Ok(Grid::from_rows(
+            rows.iter()
+                .map(|row| row.chars().map(&mut f).collect())
+                .collect(),
+        ))
+    }
+}
+
+impl Grid<char> {
+    /// Parses a rectangular grid of `allowed` characters.
+    pub fn parse(day: u8, input: &str, allowed: &str) -> Result<Self, ParseError> {
+        Grid::parse_with(day, input, allowed, |c| c)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {:?} out of bounds", pos))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {:?} out of bounds", pos))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        &self[<(usize, usize)>::from(pos)]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        &mut self[<(usize, usize)>::from(pos)]
+    }
+}
+
+/// Writes the grid back as text, one line per row.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse(0, "abc\ndef\n", "abcdef").unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 2)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+        assert!(Grid::parse(0, "ab\nc\n", "abc").is_err());
+
+        let digits = Grid::parse_with(0, "12\n34\n", "1234", |c| c.to_digit(10).unwrap());
+        assert_eq!(digits.unwrap().row(1), [3, 4]);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut grid = grid();
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((0, 0), (1, 2)), Some((1, 2)));
+        assert_eq!(grid.step(Point::new(0, 2), Direction::E), None);
+        assert_eq!(
+            grid.step(Point::new(0, 2), Direction::S),
+            Some(Point::new(1, 2))
+        );
+        assert_eq!(grid[Point::new(1, 0)], 'd');
+        *grid.get_mut((0, 1)).unwrap() = 'x';
+        assert_eq!(grid.row(0), ['a', 'x', 'c']);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = grid();
+        let n4: Vec<_> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(n4, [(0, 1), (1, 0)]);
+        let n8: Vec<_> = grid.neighbours8((1, 1)).collect();
+        assert_eq!(n8, [(0, 0), (0, 1), (0, 2), (1, 0), (1, 2)]);
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = grid();
+        let rows: Vec<&[char]> = grid.rows().collect();
+        assert_eq!(rows, [['a', 'b', 'c'], ['d', 'e', 'f']]);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.find(|c| *c == 'e'), Some((1, 1)));
+        assert_eq!(grid.positions().count(), 6);
+        assert_eq!(
+            grid.map(|c| c.to_ascii_uppercase()).to_string(),
+            "ABC\nDEF\n"
+        );
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+    }
+}
@@ -0,0 +1,155 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+/// Where a puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    Example,
+}
+
+impl Source {
+    /// `-` means stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> Source {
+        if arg == "-" {
+            Source::Stdin
+        } else {
+            Source::File(PathBuf::from(arg))
+        }
+    }
+
+    /// Picks the source from the command line: `--example` wins, then an
+    /// explicit path (or `-`), then the day's default input file.
+    pub fn select<P: Into<PathBuf>>(example: bool, arg: Option<&str>, default: P) -> Source {
+        match (example, arg) {
+            (true, _) => Source::Example,
+            (false, Some(arg)) => Source::from_arg(arg),
+            (false, None) => Source::File(default.into()),
+        }
+    }
+
+    pub fn load(&self, example: &str) -> Result<String, InputError> {
+        let text = match self {
+            Source::File(path) => read_input(path)?,
+            Source::Stdin => {
+                let mut text = String::new();
+                io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(|source| InputError::new("<stdin>", source))?;
+                text
+            }
+            Source::Example => example.to_string(),
+        };
+        Ok(text.replace("\r\n", "\n"))
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::File(path) => write!(f, "{}", path.display()),
+            Source::Stdin => write!(f, "<stdin>"),
+            Source::Example => write!(f, "<example>"),
+        }
+    }
+}
+
+/// An input that could not be read, along with the path we tried.
+#[derive(Debug)]
+pub struct InputError {
+    pub path: String,
+    pub source: io::Error,
+}
+
+impl InputError {
+    fn new<S: Into<String>>(path: S, source: io::Error) -> Self {
+        InputError {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot read input `{}`: {}", self.path, self.source)
+    }
+}
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// Reads a puzzle input, normalising `\r\n` line endings to `\n`.
+pub fn read_input<P>(filename: P) -> Result<String, InputError>
+where
+    P: AsRef<Path>,
+{
+    let path = filename.as_ref();
+    let text = fs::read_to_string(path)
+        .map_err(|source| InputError::new(path.display().to_string(), source))?;
+    Ok(text.replace("\r\n", "\n"))
+}
+
+/// A stable fingerprint of an input (64-bit FNV-1a, as 16 hex digits), used
+/// to tell real inputs apart without storing them.
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn test_read_input_crlf() {
+        let path = env::temp_dir().join("aoc_common_read_input_crlf.in");
+        fs::write(&path, "ab\r\ncd\n\r\nef").unwrap();
+        let text = read_input(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(text, "ab\ncd\n\nef");
+    }
+
+    #[test]
+    fn test_missing_file_names_path() {
+        let err = read_input("does/not/exist.in").unwrap_err();
+        assert_eq!(err.path, "does/not/exist.in");
+        assert!(err
+            .to_string()
+            .starts_with("cannot read input `does/not/exist.in`: "));
+    }
+
+    #[test]
+    fn test_source() {
+        assert_eq!(Source::from_arg("-"), Source::Stdin);
+        assert_eq!(
+            Source::from_arg("a.in"),
+            Source::File(PathBuf::from("a.in"))
+        );
+        assert_eq!(Source::Example.load("1\r\n2\n").unwrap(), "1\n2\n");
+        assert_eq!(Source::select(true, Some("a.in"), "d.in"), Source::Example);
+        assert_eq!(Source::select(false, Some("-"), "d.in"), Source::Stdin);
+        assert_eq!(
+            Source::select(false, None, "d.in"),
+            Source::File(PathBuf::from("d.in"))
+        );
+    }
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!(input_hash(""), "cbf29ce484222325");
+        assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+        assert_ne!(input_hash("1 2\n"), input_hash("1 2"));
+    }
+}
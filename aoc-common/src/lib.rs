@@ -0,0 +1,161 @@
+//! Shared plumbing for the daily puzzle crates: the [`Solution`] trait,
+//! input loading, command-line handling, answer printing and part dispatch.
+
+use std::env;
+use std::error::Error;
+use std::path::Path;
+use std::process::ExitCode;
+
+pub mod animate;
+mod answer;
+mod cancel;
+mod cli;
+pub mod cycle;
+mod error;
+pub mod fixtures;
+pub mod fuzz;
+mod geometry;
+mod grid;
+mod input;
+pub mod math;
+pub mod parse;
+mod part;
+pub mod property;
+pub mod render;
+mod report;
+mod rng;
+pub mod search;
+mod solution;
+pub mod timed;
+pub mod trace;
+
+pub use answer::Answer;
+pub use cancel::{CancellationToken, Cancelled};
+pub use cli::Args;
+pub use error::ParseError;
+pub use geometry::{Direction, Point};
+pub use grid::Grid;
+pub use input::{input_hash, read_input, InputError, Source};
+pub use part::{dispatch, print_answer, Part};
+pub use report::{Format, Report};
+pub use rng::Rng;
+pub use solution::{DynSolution, Erased, Solution};
+
+const USAGE: &str = "usage: aocNN [INPUT | - | --example] [--part 1|2|both] [--format text|json] \
+                     [--timeout SECS] [--render FILE.svg|FILE.ppm] [--animate [--fps N] | --frames DIR] \
+                     [-v | -vv] [--trace DAYS]";
+
+/// Entry point shared by every day's binary.
+///
+/// Reads the input named on the command line (`-` for stdin, `--example` for
+/// the worked example, `default_input` otherwise) and prints the answers.
+/// With `--timeout`, a part running longer than that many seconds is
+/// cancelled and reported as an error. `--render` also saves a picture of the input and its solution, and
+/// `--animate` or `--frames` first plays the solver at work. `-v` and `-vv`
+/// write what the solver does to stderr, as described in [`trace`].
+pub fn run<S>(default_input: &str) -> ExitCode
+where
+    S: Solution + 'static,
+    S::Input: Send + 'static,
+{
+    // `Erased` has no fields, so leaking it to get a `'static` solution is free
+    let solution: &'static Erased<S> = Box::leak(Box::new(Erased::new()));
+    match try_run(solution, env::args().skip(1), default_input) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn try_run<I>(
+    solution: &'static dyn DynSolution,
+    args: I,
+    default_input: &str,
+) -> Result<(), Box<dyn Error>>
+where
+    I: IntoIterator<Item = String>,
+{
+    let args = Args::parse(
+        args,
+        &["--example", "--animate", "-v", "-vv"],
+        &[
+            "--part",
+            "--format",
+            "--timeout",
+            "--render",
+            "--fps",
+            "--frames",
+            "--trace",
+        ],
+    )
+    .map_err(|e| format!("{}\n{}", e, USAGE))?;
+    let path = match args.positional() {
+        [] => None,
+        [path] => Some(path.as_str()),
+        _ => return Err(format!("too many arguments\n{}", USAGE).into()),
+    };
+    let part = match args.option("--part") {
+        Some(part) => part.parse::<Part>()?,
+        None => Part::Both,
+    };
+
+    let format = match args.option("--format") {
+        Some(format) => format.parse::<Format>()?,
+        None => Format::Text,
+    };
+    let timeout = match args.option("--timeout") {
+        Some(secs) => Some(timed::parse_timeout(secs)?),
+        None => None,
+    };
+    trace::Filter::from_args(&args)?.install();
+
+    // a bad image path is reported before the puzzle is solved
+    let render = args.option("--render").map(Path::new);
+    if let Some(path) = render {
+        render::ImageFormat::from_path(path)?;
+    }
+
+    let fps = match args.option("--fps") {
+        Some(fps) => fps
+            .parse::<u32>()
+            .ok()
+            .filter(|fps| *fps > 0)
+            .ok_or_else(|| format!("invalid frame rate `{}`", fps))?,
+        None => 10,
+    };
+
+    let source = Source::select(args.flag("--example"), path, default_input);
+    let input = source.load(solution.example())?;
+
+    if let Some(dir) = args.option("--frames") {
+        let mut dump = animate::FrameDump::new(Path::new(dir))?;
+        animate(solution, &input, &mut dump)?;
+        eprintln!("wrote {} frames to {}", dump.finish()?, dir);
+    } else if args.flag("--animate") {
+        animate(solution, &input, &mut animate::Terminal::new(fps))?;
+    }
+    dispatch(solution, part, &input, format, timeout)?;
+
+    if let Some(path) = render {
+        let parsed = solution.parse(&input)?;
+        let picture = solution
+            .render(parsed.as_ref())
+            .ok_or_else(|| format!("day {} has nothing to render", solution.day()))?;
+        picture.save(path)?;
+    }
+    Ok(())
+}
+
+fn animate(
+    solution: &dyn DynSolution,
+    input: &str,
+    screen: &mut dyn animate::Screen,
+) -> Result<(), Box<dyn Error>> {
+    let parsed = solution.parse(input)?;
+    if !solution.animate(parsed.as_ref(), screen) {
+        return Err(format!("day {} has no animation", solution.day()).into());
+    }
+    Ok(())
+}
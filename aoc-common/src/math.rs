@@ -0,0 +1,248 @@
+//! Number theory helpers: greatest common divisors, the Chinese remainder
+//! theorem, integer square roots and modular arithmetic.
+//!
+//! Everything is exact; functions that can overflow or have no answer
+//! return `None` instead of panicking.
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, or `None` if it does not fit in a `u64`.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Returns `(g, x, y)` such that `a * x + b * y == g`, where `g` is the
+/// non-negative greatest common divisor of `a` and `b`.
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Solves the system `x ≡ residue (mod modulus)` for every pair, returning
+/// `(x, m)` such that the solutions are exactly the numbers `≡ x (mod m)`,
+/// with `0 <= x < m`. The moduli need not be coprime. Returns `None` if the
+/// system has no solution, a modulus is zero, or `m` does not fit in a `u64`.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let (mut x, mut m): (i128, i128) = (0, 1);
+    for &(residue, modulus) in congruences {
+        if modulus == 0 {
+            return None;
+        }
+        let (residue, modulus) = (residue as i128 % modulus as i128, modulus as i128);
+        // x + m * k ≡ residue (mod modulus) needs m * k ≡ residue - x
+        let (g, inverse, _) = extended_gcd(m, modulus);
+        let diff = residue - x;
+        if diff % g != 0 {
+            return None;
+        }
+        let step = modulus / g;
+        let k = (diff / g).rem_euclid(step) as u128 * inverse.rem_euclid(step) as u128;
+        let k = (k % step as u128) as i128;
+        let next_m = m.checked_mul(step).filter(|&m| m <= u64::MAX as i128)?;
+        x = (x + m * k).rem_euclid(next_m);
+        m = next_m;
+    }
+    Some((x as u64, m as u64))
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from above converges on the floor of the root
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+/// The largest `r` with `r * r <= n`.
+pub fn isqrt_u64(n: u64) -> u64 {
+    isqrt_u128(n as u128) as u64
+}
+
+/// `(a + b) mod m`, or `None` if `m` is zero.
+pub fn mod_add(a: u64, b: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    Some(((a as u128 + b as u128) % m as u128) as u64)
+}
+
+/// `(a - b) mod m`, always in `0..m`, or `None` if `m` is zero.
+pub fn mod_sub(a: u64, b: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let (a, b) = (a % m, b % m);
+    Some(if a >= b { a - b } else { m - (b - a) })
+}
+
+/// `(a * b) mod m`, or `None` if `m` is zero.
+pub fn mod_mul(a: u64, b: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    Some((a as u128 * b as u128 % m as u128) as u64)
+}
+
+/// `base.pow(exp) mod m` by repeated squaring, or `None` if `m` is zero.
+pub fn mod_pow(mut base: u64, mut exp: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let mut result = 1 % m;
+    base %= m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mod_mul(result, base, m)?;
+        }
+        base = mod_mul(base, base, m)?;
+        exp >>= 1;
+    }
+    Some(result)
+}
+
+/// The `x` in `0..m` with `a * x ≡ 1 (mod m)`, or `None` if `a` and `m` are
+/// not coprime or `m` is zero.
+pub fn mod_inv(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a as i128, m as i128);
+    (g == 1).then(|| x.rem_euclid(m as i128) as u64)
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(17, 5), 1);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(7, 0), 7);
+        assert_eq!(gcd(0, 0), 0);
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(7, 1), Some(7));
+        assert_eq!(lcm(0, 5), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+    }
+
+    #[test]
+    fn test_extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (-240, 46), (17, 0), (0, -5), (3, 7)] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(
+                g,
+                gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64) as i128
+            );
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(crt(&[(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // non-coprime moduli, with and without a solution
+        assert_eq!(crt(&[(2, 4), (4, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[(1, 4), (2, 6)]), None);
+        // residues larger than the moduli
+        assert_eq!(crt(&[(6, 4), (10, 6)]), Some((10, 12)));
+        assert_eq!(crt(&[]), Some((0, 1)));
+        assert_eq!(crt(&[(1, 0)]), None);
+        assert_eq!(crt(&[(0, u64::MAX), (0, u64::MAX - 1)]), None);
+
+        // brute force against every combination of small moduli
+        for m1 in 1..12 {
+            for m2 in 1..12 {
+                for r1 in 0..m1 {
+                    for r2 in 0..m2 {
+                        let expected = (0..m1 * m2).find(|x| x % m1 == r1 && x % m2 == r2);
+                        let solved = crt(&[(r1, m1), (r2, m2)]);
+                        assert_eq!(solved.map(|(x, _)| x), expected);
+                        if let Some((_, m)) = solved {
+                            assert_eq!(Some(m), lcm(m1, m2));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_isqrt() {
+        for n in 0..10_000u64 {
+            let r = isqrt_u64(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({})", n);
+        }
+        assert_eq!(isqrt_u64(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt_u128(u128::MAX), u64::MAX as u128);
+        let big = (1u128 << 63) + 12_345;
+        assert_eq!(isqrt_u128(big * big), big);
+        assert_eq!(isqrt_u128(big * big - 1), big - 1);
+    }
+
+    #[test]
+    fn test_mod_add_sub() {
+        assert_eq!(mod_add(5, 9, 7), Some(0));
+        assert_eq!(mod_add(u64::MAX, u64::MAX, 10), Some(0));
+        assert_eq!(mod_add(1, 1, 0), None);
+        assert_eq!(mod_sub(3, 5, 7), Some(5));
+        assert_eq!(mod_sub(12, 5, 7), Some(0));
+        assert_eq!(mod_sub(1, 1, 0), None);
+    }
+
+    #[test]
+    fn test_mod_mul_pow() {
+        assert_eq!(
+            mod_mul(u64::MAX, u64::MAX, 1_000_000_007),
+            Some(114_944_269)
+        );
+        assert_eq!(mod_mul(3, 4, 0), None);
+        assert_eq!(mod_pow(2, 10, 1000), Some(24));
+        assert_eq!(mod_pow(5, 0, 7), Some(1));
+        assert_eq!(mod_pow(5, 0, 1), Some(0));
+        assert_eq!(mod_pow(3, 1_000_000_006, 1_000_000_007), Some(1));
+        assert_eq!(mod_pow(2, 2, 0), None);
+    }
+
+    #[test]
+    fn test_mod_inv() {
+        assert_eq!(mod_inv(3, 7), Some(5));
+        assert_eq!(mod_inv(10, 17).map(|x| x * 10 % 17), Some(1));
+        assert_eq!(mod_inv(4, 6), None);
+        assert_eq!(mod_inv(1, 0), None);
+    }
+}
@@ -0,0 +1,406 @@
+//! Helpers for writing parsers that report where the input went wrong.
+
+use std::str::FromStr;
+
+use crate::ParseError;
+
+/// A line of puzzle input together with its position, so that parsers can
+/// point at the offending column when something does not parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub day: u8,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+fn describe(token: &str) -> String {
+    if token.is_empty() {
+        "end of line".to_string()
+    } else {
+        format!("`{}`", token)
+    }
+}
+
+impl<'a> Line<'a> {
+    /// 1-based column of `token`, which must be a slice of this line.
+    pub fn column_of(&self, token: &str) -> usize {
+        let offset = (token.as_ptr() as usize).wrapping_sub(self.text.as_ptr() as usize);
+        if offset <= self.text.len() {
+            offset + 1
+        } else {
+            1
+        }
+    }
+
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// An error pointing at `token`, a slice of this line.
+    pub fn error(&self, token: &str, expected: &str) -> ParseError {
+        ParseError::new(
+            self.day,
+            self.number,
+            self.column_of(token),
+            expected,
+            describe(token),
+        )
+    }
+
+    /// An error pointing just past the last character of the line.
+    pub fn error_at_end(&self, expected: &str) -> ParseError {
+        self.error(&self.text[self.text.len()..], expected)
+    }
+
+    pub fn parse<T: FromStr>(&self, token: &'a str, expected: &str) -> Result<T, ParseError> {
+        token.parse::<T>().map_err(|_| self.error(token, expected))
+    }
+
+    /// Parses every whitespace-separated token of `text` (a slice of this line).
+    pub fn numbers<T: FromStr>(&self, text: &'a str, expected: &str) -> Result<Vec<T>, ParseError> {
+        text.split_whitespace()
+            .map(|token| self.parse(token, expected))
+            .collect()
+    }
+
+    /// Splits `text` (a slice of this line) around the first `separator`.
+    pub fn split_once(
+        &self,
+        text: &'a str,
+        separator: char,
+    ) -> Result<(&'a str, &'a str), ParseError> {
+        text.split_once(separator)
+            .ok_or_else(|| self.error(&text[text.len()..], &format!("`{}`", separator)))
+    }
+
+    /// Every integer in `text` (a slice of this line), skipping whatever
+    /// separates them. A `-` directly before a digit makes it negative.
+    pub fn integers<T: FromStr>(&self, text: &'a str) -> Result<Vec<T>, ParseError> {
+        let bytes = text.as_bytes();
+        let mut integers = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let negative = bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit);
+            if !negative && !bytes[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            i += 1;
+            while i < bytes.len() && bytes[i].is_ascii_digit() {
+                i += 1;
+            }
+            integers.push(self.parse(&text[start..i], "an integer that fits")?);
+        }
+        Ok(integers)
+    }
+
+    /// The text after the `<key>:` that starts this line.
+    pub fn value_of(&self, key: &str) -> Result<&'a str, ParseError> {
+        match self.text.split_once(':') {
+            Some((name, values)) if name == key => Ok(values),
+            Some((name, _)) => Err(self.error(name, &format!("`{}:`", key))),
+            None => Err(self.error(self.text, &format!("`{}:`", key))),
+        }
+    }
+
+    /// Parses a `<key>: <value> <value> ...` line.
+    pub fn key_values<T: FromStr>(&self, key: &str, expected: &str) -> Result<Vec<T>, ParseError> {
+        self.numbers(self.value_of(key)?, expected)
+    }
+
+    /// Checks that every character of the line is one of `allowed`.
+    pub fn check_chars(&self, allowed: &str, expected: &str) -> Result<(), ParseError> {
+        match self
+            .text
+            .char_indices()
+            .find(|(_, c)| !allowed.contains(*c))
+        {
+            Some((i, c)) => Err(self.error(&self.text[i..i + c.len_utf8()], expected)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Splits the input into numbered lines.
+pub fn lines(day: u8, input: &str) -> Vec<Line<'_>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, text)| Line {
+            day,
+            number: i + 1,
+            text,
+        })
+        .collect()
+}
+
+/// Like [`lines`], but skips blank lines, for inputs with one record per line.
+pub fn records(day: u8, input: &str) -> Vec<Line<'_>> {
+    lines(day, input)
+        .into_iter()
+        .filter(|line| !line.is_blank())
+        .collect()
+}
+
+/// A run of non-blank lines; never empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub lines: Vec<Line<'a>>,
+}
+
+impl<'a> Section<'a> {
+    pub fn first(&self) -> &Line<'a> {
+        &self.lines[0]
+    }
+
+    /// The only line of a section that should have been followed by a blank line.
+    pub fn single(&self) -> Result<&Line<'a>, ParseError> {
+        match self.lines.get(1) {
+            Some(line) => Err(line.error(line.text, "a blank line")),
+            None => Ok(self.first()),
+        }
+    }
+
+    /// Splits off a `<name><suffix>` header such as `seed-to-soil map:`,
+    /// returning the name and the lines below it.
+    pub fn named(
+        &self,
+        suffix: &str,
+        expected: &str,
+    ) -> Result<(&'a str, &[Line<'a>]), ParseError> {
+        let header = self.first();
+        match header.text.strip_suffix(suffix) {
+            Some(name) if !name.is_empty() => Ok((name, &self.lines[1..])),
+            _ => Err(header.error(header.text, expected)),
+        }
+    }
+}
+
+/// The blank-line separated sections of an input, in order.
+#[derive(Debug, Clone)]
+pub struct Sections<'a> {
+    day: u8,
+    input: &'a str,
+    rest: std::vec::IntoIter<Section<'a>>,
+}
+
+impl<'a> Sections<'a> {
+    /// The next section, or an error if the input has run out.
+    pub fn expect(&mut self, expected: &str) -> Result<Section<'a>, ParseError> {
+        self.rest
+            .next()
+            .ok_or_else(|| end_of_input(self.day, self.input, expected))
+    }
+
+    /// Checks that every section has been used.
+    pub fn end(mut self) -> Result<(), ParseError> {
+        match self.rest.next() {
+            Some(section) => {
+                let line = section.first();
+                Err(line.error(line.text, "end of input"))
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Section<'a>> {
+        self.rest.next()
+    }
+}
+
+/// Splits the input into sections separated by one or more blank lines.
+pub fn sections(day: u8, input: &str) -> Sections<'_> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut current: Vec<Line> = Vec::new();
+    for line in lines(day, input) {
+        if !line.is_blank() {
+            current.push(line);
+        } else if !current.is_empty() {
+            sections.push(Section {
+                lines: std::mem::take(&mut current),
+            });
+        }
+    }
+    if !current.is_empty() {
+        sections.push(Section { lines: current });
+    }
+    Sections {
+        day,
+        input,
+        rest: sections.into_iter(),
+    }
+}
+
+/// An error for input that ended while `expected` was still missing.
+pub fn end_of_input(day: u8, input: &str, expected: &str) -> ParseError {
+    ParseError::new(day, input.lines().count() + 1, 1, expected, "end of input")
+}
+
+/// Parses a rectangular grid made of the `allowed` characters. Blank lines
+/// around the grid are ignored.
+pub fn grid(day: u8, input: &str, allowed: &str) -> Result<Vec<String>, ParseError> {
+    let rows = records(day, input);
+    if rows.is_empty() {
+        return Err(end_of_input(day, input, "a grid"));
+    }
+    check_grid(&rows, allowed)?;
+    Ok(rows.iter().map(|row| row.text.to_string()).collect())
+}
+
+/// Checks that `rows` all have the same width and only use `allowed` characters.
+pub fn check_grid(rows: &[Line], allowed: &str) -> Result<(), ParseError> {
+    let expected = format!("one of `{}`", allowed);
+    let Some(first) = rows.first() else {
+        return Ok(());
+    };
+
+    for row in rows.iter() {
+        row.check_chars(allowed, &expected)?;
+        if row.text.len() != first.text.len() {
+            let width = format!("a row of width {}", first.text.len());
+            return Err(match row.text.get(first.text.len()..) {
+                Some(extra) => row.error(extra, &width),
+                None => row.error_at_end(&width),
+            });
+        }
+    }
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_errors() {
+        let line = Line {
+            day: 4,
+            number: 2,
+            text: "Card 1: 41 x8 | 83",
+        };
+        let (_, numbers) = line.split_once(line.text, ':').unwrap();
+        let err = line.numbers::<i32>(numbers, "a number").unwrap_err();
+        assert_eq!(err, ParseError::new(4, 2, 12, "a number", "`x8`"));
+
+        let err = line.split_once(numbers, '#').unwrap_err();
+        assert_eq!(err, ParseError::new(4, 2, 19, "`#`", "end of line"));
+    }
+
+    #[test]
+    fn test_records_skip_blank_lines() {
+        let records = records(1, "\nab\n\ncd\n");
+        let numbers: Vec<usize> = records.iter().map(|l| l.number).collect();
+        assert_eq!(numbers, [2, 4]);
+    }
+
+    #[test]
+    fn test_integers() {
+        let line = Line {
+            day: 22,
+            number: 3,
+            text: "p=0,-4 v=3-x, 12 -",
+        };
+        assert_eq!(line.integers::<i32>(line.text).unwrap(), [0, -4, 3, 12]);
+        assert!(line.integers::<u8>("none").unwrap().is_empty());
+
+        let err = line.integers::<u8>(line.text).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(22, 3, 5, "an integer that fits", "`-4`")
+        );
+    }
+
+    #[test]
+    fn test_key_values() {
+        let line = Line {
+            day: 6,
+            number: 1,
+            text: "Time:  7 15",
+        };
+        assert_eq!(line.key_values::<u32>("Time", "a time").unwrap(), [7, 15]);
+        assert_eq!(line.value_of("Time").unwrap(), "  7 15");
+        assert_eq!(
+            line.key_values::<u32>("Distance", "a distance")
+                .unwrap_err(),
+            ParseError::new(6, 1, 1, "`Distance:`", "`Time`")
+        );
+
+        let line = Line {
+            day: 6,
+            number: 2,
+            text: "Time 7",
+        };
+        assert_eq!(
+            line.value_of("Time").unwrap_err(),
+            ParseError::new(6, 2, 1, "`Time:`", "`Time 7`")
+        );
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "\nseeds: 1\n\n\na map:\n1 2\n3 4\n\nb map:\n";
+        let all: Vec<Vec<usize>> = sections(5, input)
+            .map(|s| s.lines.iter().map(|l| l.number).collect())
+            .collect();
+        assert_eq!(all, [vec![2], vec![5, 6, 7], vec![9]]);
+
+        let mut sections = sections(5, input);
+        let seeds = sections.expect("seeds").unwrap();
+        assert_eq!(seeds.single().unwrap().text, "seeds: 1");
+
+        let maps = sections.expect("a map").unwrap();
+        let (name, body) = maps.named(" map:", "a map header").unwrap();
+        assert_eq!(name, "a");
+        assert_eq!(
+            body.iter().map(|l| l.text).collect::<Vec<_>>(),
+            ["1 2", "3 4"]
+        );
+        assert_eq!(
+            maps.single().unwrap_err(),
+            ParseError::new(5, 6, 1, "a blank line", "`1 2`")
+        );
+        assert_eq!(
+            seeds.named(" map:", "a map header").unwrap_err(),
+            ParseError::new(5, 2, 1, "a map header", "`seeds: 1`")
+        );
+
+        let rest = sections.clone();
+        assert_eq!(
+            rest.end().unwrap_err(),
+            ParseError::new(5, 9, 1, "end of input", "`b map:`")
+        );
+        sections.expect("a map").unwrap();
+        assert_eq!(
+            sections.clone().expect("a map").unwrap_err(),
+            ParseError::new(5, 10, 1, "a map", "end of input")
+        );
+        assert_eq!(sections.end(), Ok(()));
+    }
+
+    #[test]
+    fn test_grid() {
+        assert_eq!(grid(16, ".#\n#.\n", ".#").unwrap(), [".#", "#."]);
+        assert_eq!(
+            grid(16, ".#\n#x\n", ".#").unwrap_err(),
+            ParseError::new(16, 2, 2, "one of `.#`", "`x`")
+        );
+        assert_eq!(
+            grid(16, ".#\n#..\n", ".#").unwrap_err(),
+            ParseError::new(16, 2, 3, "a row of width 2", "`.`")
+        );
+        assert_eq!(
+            grid(16, ".#\n#\n", ".#").unwrap_err(),
+            ParseError::new(16, 2, 2, "a row of width 2", "end of line")
+        );
+        assert_eq!(
+            grid(16, "\n", ".#").unwrap_err(),
+            ParseError::new(16, 2, 1, "a grid", "end of input")
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::timed::{self, Status};
+use crate::{input_hash, DynSolution, Format, Report};
+
+/// Which part(s) of a puzzle to run.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn includes_one(self) -> bool {
+        self != Part::Two
+    }
+
+    pub fn includes_two(self) -> bool {
+        self != Part::One
+    }
+
+    /// The selected part numbers, in order.
+    pub fn numbers(self) -> impl Iterator<Item = u8> {
+        [(1, self.includes_one()), (2, self.includes_two())]
+            .into_iter()
+            .filter_map(|(n, included)| included.then_some(n))
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "both" => Ok(Part::Both),
+            _ => Err(format!("invalid part `{}`, expected 1, 2 or both", s)),
+        }
+    }
+}
+
+/// Prints an answer in the `Answer, part N: X` format every day uses.
+pub fn print_answer<A: Display>(part: u8, answer: A) {
+    println!("Answer, part {}: {}", part, answer);
+}
+
+/// Parses the input once, then runs the selected part(s) and prints their
+/// answers in the requested format. In JSON mode a parse error is also
+/// reported on stdout, once for every selected part.
+///
+/// The parts are solved through [`timed::solve_parts`], so a part running
+/// longer than `timeout` is cancelled. A part that times out or panics is
+/// reported as an error once the other part has printed its answer.
+pub fn dispatch(
+    solution: &'static dyn DynSolution,
+    part: Part,
+    input: &str,
+    format: Format,
+    timeout: Option<Duration>,
+) -> Result<(), Box<dyn Error>> {
+    let hash = input_hash(input);
+    let parsed = match solution.parse(input) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            if format == Format::Json {
+                for n in part.numbers() {
+                    let report = Report {
+                        day: solution.day(),
+                        part: n,
+                        answer: None,
+                        elapsed_ns: 0,
+                        input_hash: hash.clone(),
+                        error: Some(err.to_string()),
+                    };
+                    println!("{}", report.to_json());
+                }
+            }
+            return Err(err.into());
+        }
+    };
+
+    let mut failures = Vec::new();
+    for outcome in timed::solve_parts(solution, parsed, &hash, part, timeout) {
+        match (format, &outcome.answer) {
+            (Format::Json, _) => println!("{}", outcome.report().to_json()),
+            (Format::Text, Some(answer)) => print_answer(outcome.part, answer),
+            (Format::Text, None) => {}
+        }
+        if outcome.status != Status::Ok {
+            failures.push(format!("part {} {}", outcome.part, outcome.status));
+        }
+    }
+    if !failures.is_empty() {
+        return Err(failures.join(", ").into());
+    }
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_part() {
+        assert_eq!("1".parse::<Part>(), Ok(Part::One));
+        assert_eq!("2".parse::<Part>(), Ok(Part::Two));
+        assert_eq!("both".parse::<Part>(), Ok(Part::Both));
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn test_includes() {
+        assert!(Part::One.includes_one() && !Part::One.includes_two());
+        assert!(!Part::Two.includes_one() && Part::Two.includes_two());
+        assert!(Part::Both.includes_one() && Part::Both.includes_two());
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(Part::One.numbers().collect::<Vec<_>>(), [1]);
+        assert_eq!(Part::Two.numbers().collect::<Vec<_>>(), [2]);
+        assert_eq!(Part::Both.numbers().collect::<Vec<_>>(), [1, 2]);
+    }
+}
@@ -0,0 +1,290 @@
+//! Property-based tests: run a check on many random inputs and, when one
+//! fails, shrink it to a small input that still fails before reporting it.
+//!
+//! Case `i` draws its input from `Rng::new(i)`, so a reported seed always
+//! reproduces the same input. Panics count as failures.
+
+use std::fmt::{self, Debug};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::timed::panic_message;
+use crate::Rng;
+
+/// How many random inputs a property is usually checked against.
+pub const CASES: usize = 200;
+
+// an upper bound on the candidates tried while shrinking, in case a shrinker
+// keeps producing failing inputs that are not actually smaller
+const SHRINK_ATTEMPTS: usize = 10_000;
+
+fn run<T, P>(property: &P, value: &T) -> Result<(), String>
+where
+    P: Fn(&T) -> Result<(), String>,
+{
+    panic::catch_unwind(AssertUnwindSafe(|| property(value)))
+        .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(&*payload))))
+}
+
+// Greedily replaces the failing value with the first smaller candidate that
+// still fails, until none does. Returns the value, its failure and the number
+// of successful shrinks.
+fn shrink_failure<T, S, P>(
+    mut value: T,
+    mut message: String,
+    shrink: S,
+    property: P,
+) -> (T, String, usize)
+where
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Result<(), String>,
+{
+    let mut attempts = 0..SHRINK_ATTEMPTS;
+    let mut steps = 0;
+    while let Some((candidate, failure)) =
+        shrink(&value)
+            .into_iter()
+            .zip(&mut attempts)
+            .find_map(|(candidate, _)| {
+                let failure = run(&property, &candidate).err()?;
+                Some((candidate, failure))
+            })
+    {
+        (value, message) = (candidate, failure);
+        steps += 1;
+    }
+    (value, message, steps)
+}
+
+/// A failing input after shrinking, with what went wrong for it.
+#[derive(Debug)]
+pub struct Failure<T> {
+    /// The seed of the case that first failed.
+    pub seed: u64,
+    /// How many times the input was shrunk.
+    pub steps: usize,
+    pub input: T,
+    pub message: String,
+}
+
+impl<T: Debug> fmt::Display for Failure<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "property failed for seed {} (shrunk {} times)\ninput: {:?}\n{}",
+            self.seed, self.steps, self.input, self.message
+        )
+    }
+}
+
+/// Runs `property` on `cases` inputs made by `generate` and returns the
+/// first failure, shrunk. `shrink` lists strictly smaller variants of an
+/// input, most aggressive first.
+pub fn find_failure<T, G, S, P>(
+    cases: usize,
+    generate: G,
+    shrink: S,
+    property: P,
+) -> Option<Failure<T>>
+where
+    G: Fn(&mut Rng) -> T,
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Result<(), String>,
+{
+    (0..cases as u64).find_map(|seed| {
+        let input = generate(&mut Rng::new(seed));
+        let message = run(&property, &input).err()?;
+        let (input, message, steps) = shrink_failure(input, message, &shrink, &property);
+        Some(Failure {
+            seed,
+            steps,
+            input,
+            message,
+        })
+    })
+}
+
+/// Like [`find_failure`], but panics with the failure.
+pub fn check<T, G, S, P>(cases: usize, generate: G, shrink: S, property: P)
+where
+    T: Debug,
+    G: Fn(&mut Rng) -> T,
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Result<(), String>,
+{
+    if let Some(failure) = find_failure(cases, generate, shrink, property) {
+        panic!("{}", failure);
+    }
+}
+
+/// Checks that `optimised` and a simple but slow `oracle` agree on `cases`
+/// inputs made by `generate`, like [`check`].
+pub fn equivalent<T, A, G, S, F, O>(cases: usize, generate: G, shrink: S, optimised: F, oracle: O)
+where
+    T: Debug,
+    A: PartialEq + Debug,
+    G: Fn(&mut Rng) -> T,
+    S: Fn(&T) -> Vec<T>,
+    F: Fn(&T) -> A,
+    O: Fn(&T) -> A,
+{
+    check(cases, generate, shrink, |value| {
+        let (fast, slow) = (optimised(value), oracle(value));
+        if fast == slow {
+            Ok(())
+        } else {
+            Err(format!("optimised: {:?}\noracle:    {:?}", fast, slow))
+        }
+    })
+}
+
+/// Smaller numbers to try in place of `n`, closest to zero first.
+pub fn shrink_number(n: u64) -> Vec<u64> {
+    let mut candidates = vec![0, n / 2, n.saturating_sub(1)];
+    candidates.dedup();
+    candidates.retain(|&c| c < n);
+    candidates
+}
+
+/// Smaller lists to try in place of `items`: each half, the list without
+/// each item, then the list with each item shrunk by `shrink_item`.
+pub fn shrink_vec<T, S>(items: &[T], shrink_item: S) -> Vec<Vec<T>>
+where
+    T: Clone,
+    S: Fn(&T) -> Vec<T>,
+{
+    let mut candidates = Vec::new();
+    if items.len() > 1 {
+        let (front, back) = items.split_at(items.len() / 2);
+        candidates.push(front.to_vec());
+        candidates.push(back.to_vec());
+    }
+    for i in 0..items.len() {
+        let mut without = items.to_vec();
+        without.remove(i);
+        candidates.push(without);
+    }
+    for (i, item) in items.iter().enumerate() {
+        for smaller in shrink_item(item) {
+            let mut shrunk = items.to_vec();
+            shrunk[i] = smaller;
+            candidates.push(shrunk);
+        }
+    }
+    candidates
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at_most(limit: u64) -> impl Fn(&u64) -> Result<(), String> {
+        move |&n| {
+            if n <= limit {
+                Ok(())
+            } else {
+                Err(format!("{} is too big", n))
+            }
+        }
+    }
+
+    #[test]
+    fn test_shrink_number() {
+        assert_eq!(shrink_number(0), Vec::<u64>::new());
+        assert_eq!(shrink_number(1), [0]);
+        assert_eq!(shrink_number(2), [0, 1]);
+        assert_eq!(shrink_number(10), [0, 5, 9]);
+    }
+
+    #[test]
+    fn test_shrink_vec() {
+        let candidates = shrink_vec(&[3, 1], |&n| shrink_number(n));
+        assert_eq!(
+            candidates,
+            [
+                vec![3],
+                vec![1],
+                vec![1],
+                vec![3],
+                vec![0, 1],
+                vec![1, 1],
+                vec![2, 1],
+                vec![3, 0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_shrink_failure() {
+        let (n, message, steps) =
+            shrink_failure(123_456, String::new(), |&n| shrink_number(n), at_most(999));
+        assert_eq!((n, message.as_str()), (1000, "1000 is too big"));
+        assert!(steps > 0);
+
+        // a list fails when any number in it is over 9, so one 10 is enough
+        let (items, _, _) = shrink_failure(
+            vec![4, 17, 0, 250, 9],
+            String::new(),
+            |items| shrink_vec(items, |&n| shrink_number(n)),
+            |items| items.iter().try_for_each(at_most(9)),
+        );
+        assert_eq!(items, [10]);
+    }
+
+    #[test]
+    fn test_check() {
+        check(
+            CASES,
+            |rng| rng.below(1000),
+            |&n| shrink_number(n),
+            at_most(999),
+        );
+
+        let failure = panic::catch_unwind(|| {
+            check(
+                CASES,
+                |rng| rng.below(1000),
+                |&n| shrink_number(n),
+                at_most(99),
+            )
+        })
+        .unwrap_err();
+        let message = panic_message(&*failure);
+        assert!(
+            message.contains("\ninput: 100\n100 is too big"),
+            "{}",
+            message
+        );
+
+        // panics are failures too, and are shrunk like any other
+        let failure = panic::catch_unwind(|| {
+            check(
+                CASES,
+                |rng| rng.range(10..1000),
+                |&n| shrink_number(n),
+                |&n| {
+                    assert!(n < 5, "over four");
+                    Ok(())
+                },
+            )
+        })
+        .unwrap_err();
+        let message = panic_message(&*failure);
+        assert!(
+            message.contains("\ninput: 5\npanicked: over four"),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_equivalent() {
+        equivalent(
+            CASES,
+            |rng| rng.below(1 << 20),
+            |&n| shrink_number(n),
+            |&n| n.count_ones(),
+            |&n| format!("{:b}", n).matches('1').count() as u32,
+        );
+    }
+}
@@ -0,0 +1,321 @@
+//! Pictures of grids, written as SVG or PPM images.
+//!
+//! A [`Picture`] pairs a grid of characters, usually the puzzle input, with
+//! a grid of [`Class`]es that say what every cell is: part of a loop,
+//! energised, a rock that rolled away. SVG images draw every cell in its
+//! class's colour with the character on top; PPM images only have the
+//! colours, one square of pixels per cell. The terminal gets the
+//! characters on coloured backgrounds, through ANSI escape codes.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+use crate::Grid;
+
+/// The size of a cell in an SVG image, in user units.
+const SVG_CELL: usize = 12;
+
+/// The size of a cell in a PPM image, in pixels.
+pub const PPM_CELL: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Colour {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Colour {
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Colour {
+        Colour { r, g, b }
+    }
+
+    /// The colour as `#rrggbb`.
+    pub fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+/// What a cell is, as far as the picture is concerned. The name becomes the
+/// cell's CSS class in SVG images, so it should be a plain identifier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Class {
+    pub name: &'static str,
+    pub colour: Colour,
+}
+
+impl Class {
+    pub const fn new(name: &'static str, colour: Colour) -> Class {
+        Class { name, colour }
+    }
+}
+
+/// The image formats a [`Picture`] can be saved in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Svg,
+    Ppm,
+}
+
+impl ImageFormat {
+    /// The format that `path`'s extension asks for.
+    pub fn from_path(path: &Path) -> Result<ImageFormat, String> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("svg") => Ok(ImageFormat::Svg),
+            Some("ppm") => Ok(ImageFormat::Ppm),
+            _ => Err(format!(
+                "cannot tell the image format of `{}`, expected a .svg or .ppm file",
+                path.display()
+            )),
+        }
+    }
+}
+
+/// A grid of characters, each drawn in the colour of its class.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    text: Grid<char>,
+    classes: Grid<Class>,
+}
+
+fn escape(c: char) -> String {
+    match c {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        _ => c.to_string(),
+    }
+}
+
+impl Picture {
+    /// Pairs `text` with the `classes` of its cells, which must be a grid of
+    /// the same size.
+    pub fn new(text: Grid<char>, classes: Grid<Class>) -> Picture {
+        assert!(
+            (text.width(), text.height()) == (classes.width(), classes.height()),
+            "a picture's text and classes must be the same size"
+        );
+        Picture { text, classes }
+    }
+
+    /// A picture of `text` with each cell's class picked by `classify`.
+    pub fn classify<F>(text: Grid<char>, mut classify: F) -> Picture
+    where
+        F: FnMut((usize, usize), char) -> Class,
+    {
+        let classes = Grid::from_rows(
+            text.rows()
+                .enumerate()
+                .map(|(i, row)| {
+                    row.iter()
+                        .enumerate()
+                        .map(|(j, &c)| classify((i, j), c))
+                        .collect()
+                })
+                .collect(),
+        );
+        Picture::new(text, classes)
+    }
+
+    pub fn text(&self) -> &Grid<char> {
+        &self.text
+    }
+
+    pub fn classes(&self) -> &Grid<Class> {
+        &self.classes
+    }
+
+    /// The picture as an SVG document. Runs of cells of the same class are
+    /// drawn as one rectangle, and blank cells get no text.
+    pub fn to_svg(&self) -> String {
+        let (width, height) = (self.text.width(), self.text.height());
+        let styles: BTreeMap<&str, Colour> = self
+            .classes
+            .iter()
+            .map(|(_, class)| (class.name, class.colour))
+            .collect();
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\">",
+            w = width * SVG_CELL,
+            h = height * SVG_CELL
+        );
+        svg.push_str("<style>");
+        for (name, colour) in &styles {
+            let _ = write!(svg, ".{}{{fill:{}}}", name, colour.hex());
+        }
+        let _ = writeln!(
+            svg,
+            "text{{fill:#202020;font:{}px monospace;text-anchor:middle}}</style>",
+            SVG_CELL * 3 / 4
+        );
+
+        for (i, row) in self.classes.rows().enumerate() {
+            let mut j = 0;
+            while j < width {
+                let run = row[j..].iter().take_while(|&&c| c == row[j]).count();
+                let _ = writeln!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" class=\"{}\"/>",
+                    j * SVG_CELL,
+                    i * SVG_CELL,
+                    run * SVG_CELL,
+                    SVG_CELL,
+                    row[j].name
+                );
+                j += run;
+            }
+        }
+        for ((i, j), &c) in self.text.iter() {
+            if !c.is_whitespace() {
+                let _ = writeln!(
+                    svg,
+                    "<text x=\"{}\" y=\"{}\">{}</text>",
+                    j * SVG_CELL + SVG_CELL / 2,
+                    i * SVG_CELL + SVG_CELL * 3 / 4,
+                    escape(c)
+                );
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    /// The picture as a binary PPM image, with every cell a `scale` by
+    /// `scale` square of its class's colour.
+    pub fn to_ppm(&self, scale: usize) -> Vec<u8> {
+        let (width, height) = (self.classes.width(), self.classes.height());
+        let mut ppm = format!("P6\n{} {}\n255\n", width * scale, height * scale).into_bytes();
+        for row in self.classes.rows() {
+            let line: Vec<u8> = row
+                .iter()
+                .flat_map(|class| {
+                    let Colour { r, g, b } = class.colour;
+                    [r, g, b].repeat(scale)
+                })
+                .collect();
+            for _ in 0..scale {
+                ppm.extend_from_slice(&line);
+            }
+        }
+        ppm
+    }
+
+    /// The picture as lines of text for a terminal, with every cell's
+    /// background set to its class's colour in 24-bit ANSI escape codes.
+    pub fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+        for (text, classes) in self.text.rows().zip(self.classes.rows()) {
+            let mut last = None;
+            for (&c, class) in text.iter().zip(classes) {
+                if last != Some(class.colour) {
+                    let Colour { r, g, b } = class.colour;
+                    let _ = write!(ansi, "\x1b[48;2;{};{};{};38;2;32;32;32m", r, g, b);
+                    last = Some(class.colour);
+                }
+                ansi.push(c);
+            }
+            ansi.push_str("\x1b[0m\n");
+        }
+        ansi
+    }
+
+    /// Writes the picture to `path`, in the format its extension names.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let bytes = match ImageFormat::from_path(path)? {
+            ImageFormat::Svg => self.to_svg().into_bytes(),
+            ImageFormat::Ppm => self.to_ppm(PPM_CELL),
+        };
+        fs::write(path, bytes).map_err(|err| format!("cannot write `{}`: {}", path.display(), err))
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WALL: Class = Class::new("wall", Colour::rgb(0, 0, 0));
+    const FLOOR: Class = Class::new("floor", Colour::rgb(255, 255, 255));
+    const PATH: Class = Class::new("path", Colour::rgb(255, 0, 16));
+
+    fn picture() -> Picture {
+        let text = Grid::parse(0, "#.>\n#&.\n", "#.>&").unwrap();
+        Picture::classify(text, |(i, j), c| match c {
+            '#' => WALL,
+            _ if i == 0 || j == 2 => PATH,
+            _ => FLOOR,
+        })
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(Colour::rgb(255, 0, 16).hex(), "#ff0010");
+    }
+
+    #[test]
+    fn test_image_format() {
+        let format = |path: &str| ImageFormat::from_path(Path::new(path));
+        assert_eq!(format("out.svg"), Ok(ImageFormat::Svg));
+        assert_eq!(format("dir/out.ppm"), Ok(ImageFormat::Ppm));
+        assert!(format("out.png").is_err());
+        assert!(format("out").is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "the same size")]
+    fn test_mismatched_sizes() {
+        Picture::new(Grid::new(2, 2, '.'), Grid::new(2, 3, FLOOR));
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let svg = picture().to_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"36\""));
+        assert!(svg.contains("<style>.floor{fill:#ffffff}.path{fill:#ff0010}.wall{fill:#000000}"));
+        // the path on the first row is one rectangle
+        assert!(svg.contains("<rect x=\"12\" y=\"0\" width=\"24\" height=\"12\" class=\"path\"/>"));
+        assert_eq!(svg.matches("<rect").count(), 5);
+        assert!(svg.contains(">&gt;</text>") && svg.contains(">&amp;</text>"));
+        assert!(svg.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_to_ppm() {
+        let ppm = picture().to_ppm(2);
+        let header = b"P6\n6 4\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        let pixels = &ppm[header.len()..];
+        assert_eq!(pixels.len(), 6 * 4 * 3);
+        // the second pixel row repeats the first, wall then path
+        assert_eq!(&pixels[..6], [0, 0, 0, 0, 0, 0]);
+        assert_eq!(&pixels[6..9], [255, 0, 16]);
+        assert_eq!(pixels[..18], pixels[18..36]);
+    }
+
+    #[test]
+    fn test_to_ansi() {
+        let ansi = picture().to_ansi();
+        let lines: Vec<&str> = ansi.lines().collect();
+        assert_eq!(
+            lines[0],
+            "\x1b[48;2;0;0;0;38;2;32;32;32m#\x1b[48;2;255;0;16;38;2;32;32;32m.>\x1b[0m"
+        );
+        assert_eq!(lines.len(), 2);
+    }
+
+    #[test]
+    fn test_save() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!("aoc_render_{}.svg", std::process::id()));
+        picture().save(&path).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), picture().to_svg());
+        fs::remove_file(&path).unwrap();
+        assert!(picture().save(&dir.join("picture.gif")).is_err());
+    }
+}
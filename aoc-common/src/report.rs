@@ -0,0 +1,72 @@
+use std::str::FromStr;
+
+use serde::Serialize;
+
+/// How answers are written to stdout.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Format {
+    /// `Answer, part N: X` lines.
+    #[default]
+    Text,
+    /// One [`Report`] object per line.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("invalid format `{}`, expected text or json", s)),
+        }
+    }
+}
+
+/// The outcome of running one part of one day, as printed by `--format json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    /// Time spent solving the part, excluding parsing.
+    pub elapsed_ns: u64,
+    pub input_hash: String,
+    pub error: Option<String>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("reports always serialize")
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("text".parse::<Format>(), Ok(Format::Text));
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_to_json() {
+        let report = Report {
+            day: 6,
+            part: 2,
+            answer: Some("71503".to_string()),
+            elapsed_ns: 1200,
+            input_hash: "00ff".to_string(),
+            error: None,
+        };
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":6,"part":2,"answer":"71503","elapsed_ns":1200,"input_hash":"00ff","error":null}"#
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! Breadth-first, Dijkstra and A* search over any [`Graph`].
+//!
+//! Every search calls a visitor with each state once its final distance is
+//! known; returning [`ControlFlow::Break`] from the visitor stops the search
+//! early, for example once a goal has been reached.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::ControlFlow;
+
+/// A graph given implicitly by the neighbours of each state.
+pub trait Graph {
+    type State: Clone + Eq + Hash;
+
+    fn neighbours(&self, state: &Self::State) -> Vec<Self::State>;
+
+    /// The cost of moving from `from` to its neighbour `to`. Breadth-first
+    /// search ignores it and counts every move as 1.
+    fn cost(&self, _from: &Self::State, _to: &Self::State) -> usize {
+        1
+    }
+}
+
+// Bookkeeping for one state the search has reached.
+#[derive(Debug, Clone)]
+struct Node {
+    dist: usize,
+    parent: Option<usize>,
+    settled: bool,
+}
+
+/// The outcome of a search: distances from the nearest start, and enough
+/// bookkeeping to reconstruct the paths behind them.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    // every reached state, numbered in the order they were reached
+    ids: HashMap<S, usize>,
+    states: Vec<S>,
+    nodes: Vec<Node>,
+    stopped_at: Option<usize>,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    fn new() -> Self {
+        Search {
+            ids: HashMap::new(),
+            states: Vec::new(),
+            nodes: Vec::new(),
+            stopped_at: None,
+        }
+    }
+
+    // the id of `state`, which is added unsettled at distance `dist` if new
+    fn reach(&mut self, state: &S, dist: usize) -> (usize, bool) {
+        if let Some(&id) = self.ids.get(state) {
+            return (id, false);
+        }
+        let id = self.nodes.len();
+        self.ids.insert(state.clone(), id);
+        self.states.push(state.clone());
+        self.nodes.push(Node {
+            dist,
+            parent: None,
+            settled: false,
+        });
+        (id, true)
+    }
+
+    fn settled(&self, state: &S) -> Option<&Node> {
+        let node = &self.nodes[*self.ids.get(state)?];
+        node.settled.then_some(node)
+    }
+
+    pub fn distance(&self, state: &S) -> Option<usize> {
+        self.settled(state).map(|node| node.dist)
+    }
+
+    /// Every state the search settled, with its distance.
+    pub fn distances(&self) -> impl Iterator<Item = (&S, usize)> {
+        self.states
+            .iter()
+            .zip(self.nodes.iter())
+            .filter(|(_, node)| node.settled)
+            .map(|(state, node)| (state, node.dist))
+    }
+
+    /// The state whose visit stopped the search, if it stopped early.
+    pub fn stopped_at(&self) -> Option<&S> {
+        self.stopped_at.map(|id| &self.states[id])
+    }
+
+    /// The states on a shortest path from a start to `to`, both included.
+    pub fn path(&self, to: &S) -> Option<Vec<S>> {
+        let mut node = self.settled(to)?;
+        let mut path = vec![to.clone()];
+        while let Some(parent) = node.parent {
+            path.push(self.states[parent].clone());
+            node = &self.nodes[parent];
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth-first search from `starts`, counting every move as 1.
+pub fn bfs<G, V>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::State>,
+    mut visit: V,
+) -> Search<G::State>
+where
+    G: Graph,
+    V: FnMut(&G::State, usize) -> ControlFlow<()>,
+{
+    // the first time a state is reached is along a shortest path, so states
+    // are settled as soon as they are queued
+    let mut search = Search::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        let (id, new) = search.reach(&start, 0);
+        if new {
+            search.nodes[id].settled = true;
+            queue.push_back(id);
+        }
+    }
+
+    while let Some(id) = queue.pop_front() {
+        let dist = search.nodes[id].dist;
+        let state = search.states[id].clone();
+        if visit(&state, dist).is_break() {
+            search.stopped_at = Some(id);
+            break;
+        }
+        for next in graph.neighbours(&state) {
+            let (next_id, new) = search.reach(&next, dist + 1);
+            if new {
+                let node = &mut search.nodes[next_id];
+                node.parent = Some(id);
+                node.settled = true;
+                queue.push_back(next_id);
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's shortest-path search from `starts`.
+pub fn dijkstra<G, V>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::State>,
+    visit: V,
+) -> Search<G::State>
+where
+    G: Graph,
+    V: FnMut(&G::State, usize) -> ControlFlow<()>,
+{
+    astar(graph, starts, |_| 0, visit)
+}
+
+/// A* search from `starts`. The `heuristic` estimates the remaining distance
+/// to the goal; it must never overestimate it, nor drop by more than the cost
+/// of a move, or the distances found may not be the shortest.
+pub fn astar<G, H, V>(
+    graph: &G,
+    starts: impl IntoIterator<Item = G::State>,
+    heuristic: H,
+    mut visit: V,
+) -> Search<G::State>
+where
+    G: Graph,
+    H: Fn(&G::State) -> usize,
+    V: FnMut(&G::State, usize) -> ControlFlow<()>,
+{
+    let mut search = Search::new();
+    // entries go stale when a shorter way to their state is found, and are
+    // skipped once that state is settled
+    let mut queue: BinaryHeap<Reverse<(usize, usize)>> = BinaryHeap::new();
+    for start in starts {
+        let (id, new) = search.reach(&start, 0);
+        if new {
+            queue.push(Reverse((heuristic(&start), id)));
+        }
+    }
+
+    while let Some(Reverse((_, id))) = queue.pop() {
+        if search.nodes[id].settled {
+            continue;
+        }
+        search.nodes[id].settled = true;
+        let dist = search.nodes[id].dist;
+        let state = search.states[id].clone();
+        if visit(&state, dist).is_break() {
+            search.stopped_at = Some(id);
+            break;
+        }
+        for next in graph.neighbours(&state) {
+            let next_dist = dist + graph.cost(&state, &next);
+            let (next_id, new) = search.reach(&next, next_dist);
+            let node = &mut search.nodes[next_id];
+            if new || (!node.settled && next_dist < node.dist) {
+                node.dist = next_dist;
+                node.parent = Some(id);
+                queue.push(Reverse((next_dist + heuristic(&next), next_id)));
+            }
+        }
+    }
+    search
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a line of numbers where each number can step to its neighbours, moving
+    // up costing the number moved to
+    struct Line(usize);
+
+    impl Graph for Line {
+        type State = usize;
+
+        fn neighbours(&self, n: &usize) -> Vec<usize> {
+            [n.checked_sub(1), Some(n + 1).filter(|&m| m < self.0)]
+                .into_iter()
+                .flatten()
+                .collect()
+        }
+
+        fn cost(&self, from: &usize, to: &usize) -> usize {
+            if to > from {
+                *to
+            } else {
+                1
+            }
+        }
+    }
+
+    fn explore(_: &usize, _: usize) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+
+    #[test]
+    fn test_bfs() {
+        let search = bfs(&Line(5), [2], explore);
+        assert_eq!(search.distance(&0), Some(2));
+        assert_eq!(search.distance(&4), Some(2));
+        assert_eq!(search.distances().count(), 5);
+        assert_eq!(search.path(&0), Some(vec![2, 1, 0]));
+        assert_eq!(search.stopped_at(), None);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let search = dijkstra(&Line(5), [0], explore);
+        assert_eq!(search.distance(&4), Some(1 + 2 + 3 + 4));
+        assert_eq!(search.path(&3), Some(vec![0, 1, 2, 3]));
+
+        // with two starts every state is measured from the nearest one
+        let search = dijkstra(&Line(5), [0, 4], explore);
+        assert_eq!(search.distance(&3), Some(1));
+        assert_eq!(search.path(&3), Some(vec![4, 3]));
+        assert_eq!(search.distance(&2), Some(2));
+    }
+
+    #[test]
+    fn test_astar() {
+        let search = astar(
+            &Line(100),
+            [0],
+            |n| 99 - n,
+            |n, _| {
+                if *n == 99 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            },
+        );
+        assert_eq!(search.stopped_at(), Some(&99));
+        assert_eq!(search.distance(&99), Some((1..100).sum()));
+        assert_eq!(search.path(&99).unwrap().len(), 100);
+    }
+
+    #[test]
+    fn test_early_stop() {
+        let mut visited = vec![];
+        let search = bfs(&Line(10), [0], |n, dist| {
+            visited.push(*n);
+            if dist == 3 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert_eq!(visited, [0, 1, 2, 3]);
+        assert_eq!(search.stopped_at(), Some(&3));
+        assert_eq!(search.path(&9), None);
+    }
+}
@@ -0,0 +1,230 @@
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::animate::Screen;
+use crate::render::Picture;
+use crate::{Answer, CancellationToken, Cancelled, ParseError, Rng};
+
+/// A day's puzzle: parse the input once, then solve both parts from it.
+pub trait Solution {
+    const DAY: u8;
+    /// The puzzle's worked example, used by `--example`.
+    const EXAMPLE: &'static str;
+
+    type Input;
+    type Answer: Into<Answer>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer;
+    fn part2(input: &Self::Input) -> Self::Answer;
+
+    /// A random valid input of roughly `size` records (or grid rows), for
+    /// stress tests and benchmarks. Both parts must have an answer for it.
+    fn generate(rng: &mut Rng, size: usize) -> String;
+
+    /// Like [`part1`](Solution::part1), but gives up once `token` is
+    /// cancelled. Only solvers with potentially long loops override this.
+    fn part1_cancellable(
+        input: &Self::Input,
+        token: &CancellationToken,
+    ) -> Result<Self::Answer, Cancelled> {
+        token.check()?;
+        Ok(Self::part1(input))
+    }
+
+    /// Like [`part2`](Solution::part2), but gives up once `token` is cancelled.
+    fn part2_cancellable(
+        input: &Self::Input,
+        token: &CancellationToken,
+    ) -> Result<Self::Answer, Cancelled> {
+        token.check()?;
+        Ok(Self::part2(input))
+    }
+
+    /// A picture of the input with the solution drawn over it, for
+    /// `--render`. Only days whose input is a grid override this.
+    fn render(_input: &Self::Input) -> Option<Picture> {
+        None
+    }
+
+    /// Shows the solver at work on `screen`, one frame per step, for
+    /// `--animate`. Returns false for days that have no animation.
+    fn animate(_input: &Self::Input, _screen: &mut dyn Screen) -> bool {
+        false
+    }
+}
+
+/// Object-safe view of a [`Solution`], so days with different input types
+/// can sit side by side in a registry.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn example(&self) -> &'static str;
+    fn generate(&self, seed: u64, size: usize) -> String;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send>, ParseError>;
+    fn part1(&self, input: &(dyn Any + Send)) -> Answer;
+    fn part2(&self, input: &(dyn Any + Send)) -> Answer;
+    fn part1_cancellable(
+        &self,
+        input: &(dyn Any + Send),
+        token: &CancellationToken,
+    ) -> Result<Answer, Cancelled>;
+    fn part2_cancellable(
+        &self,
+        input: &(dyn Any + Send),
+        token: &CancellationToken,
+    ) -> Result<Answer, Cancelled>;
+    fn render(&self, input: &(dyn Any + Send)) -> Option<Picture>;
+    fn animate(&self, input: &(dyn Any + Send), screen: &mut dyn Screen) -> bool;
+}
+
+/// Adapts a [`Solution`] to [`DynSolution`].
+pub struct Erased<S>(PhantomData<fn() -> S>);
+
+impl<S> Erased<S> {
+    pub const fn new() -> Self {
+        Erased(PhantomData)
+    }
+}
+
+impl<S> Default for Erased<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn downcast<S: Solution>(input: &(dyn Any + Send)) -> &S::Input
+where
+    S::Input: 'static,
+{
+    input
+        .downcast_ref::<S::Input>()
+        .expect("input was parsed by a different solution")
+}
+
+impl<S> DynSolution for Erased<S>
+where
+    S: Solution,
+    S::Input: Send + 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn example(&self) -> &'static str {
+        S::EXAMPLE
+    }
+
+    fn generate(&self, seed: u64, size: usize) -> String {
+        S::generate(&mut Rng::new(seed), size)
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any + Send>, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, input: &(dyn Any + Send)) -> Answer {
+        S::part1(downcast::<S>(input)).into()
+    }
+
+    fn part2(&self, input: &(dyn Any + Send)) -> Answer {
+        S::part2(downcast::<S>(input)).into()
+    }
+
+    fn part1_cancellable(
+        &self,
+        input: &(dyn Any + Send),
+        token: &CancellationToken,
+    ) -> Result<Answer, Cancelled> {
+        S::part1_cancellable(downcast::<S>(input), token).map(Into::into)
+    }
+
+    fn part2_cancellable(
+        &self,
+        input: &(dyn Any + Send),
+        token: &CancellationToken,
+    ) -> Result<Answer, Cancelled> {
+        S::part2_cancellable(downcast::<S>(input), token).map(Into::into)
+    }
+
+    fn render(&self, input: &(dyn Any + Send)) -> Option<Picture> {
+        S::render(downcast::<S>(input))
+    }
+
+    fn animate(&self, input: &(dyn Any + Send), screen: &mut dyn Screen) -> bool {
+        S::animate(downcast::<S>(input), screen)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 99;
+        const EXAMPLE: &'static str = "2 3 4";
+
+        type Input = Vec<i32>;
+        type Answer = i32;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input
+                .split_whitespace()
+                .map(|n| {
+                    n.parse()
+                        .map_err(|_| ParseError::new(Self::DAY, 1, 1, "a number", n))
+                })
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> i32 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Input) -> i32 {
+            input.iter().product()
+        }
+
+        fn generate(rng: &mut Rng, size: usize) -> String {
+            let numbers: Vec<String> = (0..size).map(|_| rng.range(1..10).to_string()).collect();
+            numbers.join(" ")
+        }
+    }
+
+    #[test]
+    fn test_erased() {
+        let solution: &dyn DynSolution = &Erased::<Sum>::new();
+        assert_eq!(solution.day(), 99);
+        let input = solution.parse(solution.example()).unwrap();
+        assert_eq!(solution.part1(input.as_ref()), Answer::Signed(9));
+        assert_eq!(solution.part2(input.as_ref()), Answer::Signed(24));
+        assert!(solution.parse("2 x").is_err());
+        assert_eq!(solution.render(input.as_ref()), None);
+    }
+
+    #[test]
+    fn test_generate() {
+        let solution: &dyn DynSolution = &Erased::<Sum>::new();
+        let input = solution.generate(5, 4);
+        assert_eq!(input, solution.generate(5, 4));
+        assert_eq!(Sum::parse(&input).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_default_cancellable() {
+        let solution: &dyn DynSolution = &Erased::<Sum>::new();
+        let input = solution.parse(solution.example()).unwrap();
+        let token = CancellationToken::new();
+        assert_eq!(
+            solution.part1_cancellable(input.as_ref(), &token),
+            Ok(Answer::Signed(9))
+        );
+        token.cancel();
+        assert_eq!(
+            solution.part2_cancellable(input.as_ref(), &token),
+            Err(Cancelled)
+        );
+    }
+}
@@ -0,0 +1,303 @@
+//! Solving parts on a background thread under a timeout, with errors, panics
+//! and timeouts turned into a [`Status`] per part. Both the `aoc` runner and
+//! the days' own binaries solve through here.
+
+use std::any::Any;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{CancellationToken, Cancelled, DynSolution, Part, Report};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    Error(String),
+    Panicked(String),
+    TimedOut,
+    /// The part never started, because an earlier part that timed out
+    /// ignored its cancellation and was still running.
+    NotRun,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Ok => write!(f, "ok"),
+            Status::Error(err) => write!(f, "error: {}", err),
+            Status::Panicked(message) => write!(f, "panicked: {}", message),
+            Status::TimedOut => write!(f, "timed out"),
+            Status::NotRun => write!(f, "not run"),
+        }
+    }
+}
+
+/// The result of running one part of one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub elapsed: Duration,
+    pub input_hash: String,
+    pub status: Status,
+}
+
+impl Outcome {
+    pub fn report(&self) -> Report {
+        Report {
+            day: self.day,
+            part: self.part,
+            answer: self.answer.clone(),
+            elapsed_ns: self.elapsed.as_nanos() as u64,
+            input_hash: self.input_hash.clone(),
+            error: match &self.status {
+                Status::Ok => None,
+                status => Some(status.to_string()),
+            },
+        }
+    }
+}
+
+/// The message a panic was raised with, if it was a string.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Parses a `--timeout` value, a positive number of seconds.
+pub fn parse_timeout(secs: &str) -> Result<Duration, String> {
+    secs.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| format!("invalid timeout `{}`", secs))
+}
+
+enum Event {
+    Started(u8),
+    Finished(u8, Option<String>, Duration, Status),
+}
+
+// runs in a detached thread, so that the caller can stop waiting for it
+fn solve_in_background(
+    solution: &'static dyn DynSolution,
+    parsed: Box<dyn Any + Send>,
+    parts: Vec<(u8, CancellationToken)>,
+    tx: Sender<Event>,
+) {
+    for (part, token) in parts {
+        let _ = tx.send(Event::Started(part));
+        let start = Instant::now();
+        let answer = panic::catch_unwind(AssertUnwindSafe(|| match part {
+            1 => solution.part1_cancellable(parsed.as_ref(), &token),
+            _ => solution.part2_cancellable(parsed.as_ref(), &token),
+        }));
+        let (answer, status) = match answer {
+            Ok(Ok(answer)) => (Some(answer.to_string()), Status::Ok),
+            Ok(Err(Cancelled)) => (None, Status::TimedOut),
+            Err(payload) => (None, Status::Panicked(panic_message(&*payload))),
+        };
+        if tx
+            .send(Event::Finished(part, answer, start.elapsed(), status))
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+fn next_event(rx: &Receiver<Event>, deadline: Option<Instant>) -> Result<Event, RecvTimeoutError> {
+    match deadline {
+        Some(deadline) => rx.recv_timeout(deadline.saturating_duration_since(Instant::now())),
+        None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    }
+}
+
+/// Solves the selected parts of already parsed input, turning panics into
+/// statuses so that one failing part does not hide the other. A part that
+/// runs longer than `timeout` is cancelled and reported as timed out. A part
+/// that ignores its cancellation token keeps running in the background, and
+/// the parts after it are reported as not run if it is still running
+/// `timeout` later.
+pub fn solve_parts(
+    solution: &'static dyn DynSolution,
+    parsed: Box<dyn Any + Send>,
+    input_hash: &str,
+    part: Part,
+    timeout: Option<Duration>,
+) -> Vec<Outcome> {
+    let parts: Vec<(u8, CancellationToken)> = part
+        .numbers()
+        .map(|n| (n, CancellationToken::new()))
+        .collect();
+    let (tx, rx) = mpsc::channel();
+    let worker_parts = parts.clone();
+    thread::spawn(move || solve_in_background(solution, parsed, worker_parts, tx));
+
+    let outcome = |part, answer, elapsed, status| Outcome {
+        day: solution.day(),
+        part,
+        answer,
+        elapsed,
+        input_hash: input_hash.to_string(),
+        status,
+    };
+    let mut outcomes = Vec::new();
+    // waiting for a part to start is subject to the timeout once an earlier
+    // part has timed out and may still be running
+    let mut start_deadline = None;
+    for (part, token) in parts {
+        let started = loop {
+            match next_event(&rx, start_deadline) {
+                Ok(Event::Started(p)) if p == part => break true,
+                Ok(_) => continue,
+                Err(_) => break false,
+            }
+        };
+        if !started {
+            outcomes.push(outcome(part, None, Duration::ZERO, Status::NotRun));
+            continue;
+        }
+
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let finished = loop {
+            match next_event(&rx, deadline) {
+                Ok(Event::Finished(p, answer, elapsed, status)) if p == part => {
+                    break Ok(outcome(part, answer, elapsed, status))
+                }
+                Ok(_) => continue,
+                Err(err) => break Err(err),
+            }
+        };
+        match finished {
+            Ok(finished) => outcomes.push(finished),
+            Err(RecvTimeoutError::Timeout) => {
+                token.cancel();
+                let elapsed = timeout.unwrap_or_default();
+                outcomes.push(outcome(part, None, elapsed, Status::TimedOut));
+                start_deadline = timeout.map(|timeout| Instant::now() + timeout);
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                let status = Status::Panicked("the solver thread stopped".to_string());
+                outcomes.push(outcome(part, None, Duration::ZERO, status));
+            }
+        }
+    }
+    outcomes
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Erased, ParseError, Rng, Solution};
+
+    struct Spin;
+
+    impl Solution for Spin {
+        const DAY: u8 = 98;
+        const EXAMPLE: &'static str = "";
+
+        type Input = ();
+        type Answer = u8;
+
+        fn parse(_: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> u8 {
+            unreachable!("only run through part1_cancellable")
+        }
+
+        fn part2(_: &Self::Input) -> u8 {
+            2
+        }
+
+        fn generate(_: &mut Rng, _: usize) -> String {
+            String::new()
+        }
+
+        fn part1_cancellable(_: &Self::Input, token: &CancellationToken) -> Result<u8, Cancelled> {
+            loop {
+                token.check()?;
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+    }
+
+    static SPIN: Erased<Spin> = Erased::new();
+
+    // a part 1 that never checks its token, so it never returns
+    struct Stuck;
+
+    impl Solution for Stuck {
+        const DAY: u8 = 97;
+        const EXAMPLE: &'static str = "";
+
+        type Input = ();
+        type Answer = u8;
+
+        fn parse(_: &str) -> Result<Self::Input, ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &Self::Input) -> u8 {
+            loop {
+                thread::sleep(Duration::from_secs(1));
+            }
+        }
+
+        fn part2(_: &Self::Input) -> u8 {
+            2
+        }
+
+        fn generate(_: &mut Rng, _: usize) -> String {
+            String::new()
+        }
+    }
+
+    static STUCK: Erased<Stuck> = Erased::new();
+
+    #[test]
+    fn test_solve_parts_timeout() {
+        let timeout = Some(Duration::from_millis(50));
+        let outcomes = solve_parts(&SPIN, Box::new(()), "", Part::Both, timeout);
+        assert_eq!(outcomes[0].status, Status::TimedOut);
+        assert_eq!(outcomes[1].status, Status::Ok);
+        assert_eq!(outcomes[1].answer.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn test_solve_parts_not_run() {
+        let timeout = Some(Duration::from_millis(50));
+        let outcomes = solve_parts(&STUCK, Box::new(()), "", Part::Both, timeout);
+        assert_eq!(outcomes[0].status, Status::TimedOut);
+        assert_eq!(outcomes[1].status, Status::NotRun);
+        assert_eq!(outcomes[1].report().error.as_deref(), Some("not run"));
+    }
+
+    #[test]
+    fn test_solve_parts_selected() {
+        let outcomes = solve_parts(&SPIN, Box::new(()), "00ff", Part::Two, None);
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!((outcomes[0].day, outcomes[0].part), (98, 2));
+        assert_eq!(outcomes[0].report().error, None);
+        assert_eq!(outcomes[0].report().input_hash, "00ff");
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("1.5"), Ok(Duration::from_millis(1500)));
+        assert!(parse_timeout("0").is_err());
+        assert!(parse_timeout("-1").is_err());
+        assert!(parse_timeout("x").is_err());
+    }
+}
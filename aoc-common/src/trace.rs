@@ -0,0 +1,256 @@
+//! Lightweight tracing of solver internals, written to stderr.
+//!
+//! Solvers call [`debug!`](crate::debug) for events worth seeing with `-v`
+//! and [`trace!`](crate::trace) for the fine-grained ones only `-vv` shows,
+//! such as cache hits. [`span!`](crate::span) marks a stretch of work: the
+//! events inside it are indented, and its end is logged with the time it
+//! took. `--trace DAYS` restricts the output to some days, which is mostly
+//! useful with `aoc --all`.
+//!
+//! Every line starts with the crate that logged it, so lines from days
+//! running on different threads can still be told apart. Tracing that is
+//! switched off costs one atomic load per call.
+
+use std::cell::Cell;
+use std::fmt;
+use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+use std::time::Instant;
+
+use crate::Args;
+
+/// How much detail an event is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// Shown with `-v`.
+    Debug = 1,
+    /// Shown with `-vv`.
+    Trace = 2,
+}
+
+/// Which events get written: those up to a verbosity, from some days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Filter {
+    verbosity: u8,
+    // bit `d` is set for every day `d` traced, and none are for all days
+    days: u32,
+}
+
+// the installed filter, split in two so that checking it takes no lock
+static VERBOSITY: AtomicU8 = AtomicU8::new(0);
+static DAYS: AtomicU32 = AtomicU32::new(0);
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+impl Filter {
+    /// Writes events up to `verbosity`, from `days` only unless that is
+    /// empty.
+    pub fn new(verbosity: u8, days: &[u8]) -> Filter {
+        Filter {
+            verbosity,
+            days: days.iter().fold(0, |mask, &day| mask | (1 << (day % 32))),
+        }
+    }
+
+    /// The filter asked for by `-v`, `-vv` and `--trace DAYS`, which must be
+    /// among the flags and options `args` was parsed with. `--trace` on its
+    /// own implies `-v`.
+    pub fn from_args(args: &Args) -> Result<Filter, String> {
+        let days = match args.option("--trace") {
+            Some(days) => days
+                .split(',')
+                .map(|day| {
+                    day.trim()
+                        .parse::<u8>()
+                        .ok()
+                        .filter(|day| (1..=25).contains(day))
+                        .ok_or_else(|| format!("invalid day `{}` to trace", day))
+                })
+                .collect::<Result<Vec<u8>, String>>()?,
+            None => Vec::new(),
+        };
+        let verbosity = if args.flag("-vv") {
+            2
+        } else if args.flag("-v") || !days.is_empty() {
+            1
+        } else {
+            0
+        };
+        Ok(Filter::new(verbosity, &days))
+    }
+
+    pub fn allows(&self, level: Level, day: Option<u8>) -> bool {
+        level as u8 <= self.verbosity
+            && (self.days == 0 || day.is_some_and(|day| self.days & (1 << (day % 32)) != 0))
+    }
+
+    /// Makes this the filter for every thread from now on.
+    pub fn install(self) {
+        VERBOSITY.store(self.verbosity, Ordering::Relaxed);
+        DAYS.store(self.days, Ordering::Relaxed);
+    }
+}
+
+// the day of a module path such as `aoc05::tests`
+fn day_of(target: &str) -> Option<u8> {
+    let name = target.split("::").next()?;
+    name.strip_prefix("aoc")?.parse().ok()
+}
+
+/// Whether an event at `level` from the module `target` gets written. Used
+/// by the macros.
+pub fn enabled(level: Level, target: &str) -> bool {
+    let verbosity = VERBOSITY.load(Ordering::Relaxed);
+    if level as u8 > verbosity {
+        return false;
+    }
+    let filter = Filter {
+        verbosity,
+        days: DAYS.load(Ordering::Relaxed),
+    };
+    filter.allows(level, day_of(target))
+}
+
+fn format_line(target: &str, depth: usize, message: fmt::Arguments) -> String {
+    let name = target.split("::").next().unwrap_or(target);
+    format!("[{}] {}{}", name, "  ".repeat(depth), message)
+}
+
+/// Writes an event from the module `target`. Used by the macros.
+pub fn emit(target: &str, message: fmt::Arguments) {
+    let depth = DEPTH.with(|depth| depth.get());
+    eprintln!("{}", format_line(target, depth, message));
+}
+
+struct Entered {
+    target: &'static str,
+    name: String,
+    start: Instant,
+}
+
+/// A stretch of work, logged when it starts and when it is dropped. Made by
+/// [`span!`](crate::span).
+#[must_use = "a span ends as soon as it is dropped"]
+pub struct Span(Option<Entered>);
+
+impl Span {
+    pub fn enter<F>(level: Level, target: &'static str, name: F) -> Span
+    where
+        F: FnOnce() -> String,
+    {
+        if !enabled(level, target) {
+            return Span(None);
+        }
+        let name = name();
+        emit(target, format_args!("> {}", name));
+        DEPTH.with(|depth| depth.set(depth.get() + 1));
+        Span(Some(Entered {
+            target,
+            name,
+            start: Instant::now(),
+        }))
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some(entered) = &self.0 {
+            DEPTH.with(|depth| depth.set(depth.get().saturating_sub(1)));
+            let elapsed = entered.start.elapsed();
+            emit(
+                entered.target,
+                format_args!("< {} ({:.1?})", entered.name, elapsed),
+            );
+        }
+    }
+}
+
+/// Writes an event at the given [`Level`], formatted like `format!`.
+#[macro_export]
+macro_rules! event {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::trace::enabled($level, module_path!()) {
+            $crate::trace::emit(module_path!(), format_args!($($arg)+));
+        }
+    };
+}
+
+/// Writes an event that `-v` shows.
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)+) => {
+        $crate::event!($crate::trace::Level::Debug, $($arg)+)
+    };
+}
+
+/// Writes an event that only `-vv` shows.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        $crate::event!($crate::trace::Level::Trace, $($arg)+)
+    };
+}
+
+/// Starts a [`Span`](crate::trace::Span) that `-v` shows, named like
+/// `format!`. It ends when the value is dropped, so bind it to a variable.
+#[macro_export]
+macro_rules! span {
+    ($($arg:tt)+) => {
+        $crate::trace::Span::enter($crate::trace::Level::Debug, module_path!(), || {
+            format!($($arg)+)
+        })
+    };
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(s: &str) -> Result<Filter, String> {
+        let args = Args::parse(
+            s.split_whitespace().map(|a| a.to_string()),
+            &["-v", "-vv"],
+            &["--trace"],
+        )?;
+        Filter::from_args(&args)
+    }
+
+    #[test]
+    fn test_from_args() {
+        assert_eq!(filter(""), Ok(Filter::default()));
+        assert_eq!(filter("-v"), Ok(Filter::new(1, &[])));
+        assert_eq!(filter("-vv --trace 5,12"), Ok(Filter::new(2, &[5, 12])));
+        assert_eq!(filter("--trace 8"), Ok(Filter::new(1, &[8])));
+        assert!(filter("--trace 0").is_err());
+        assert!(filter("--trace 5,x").is_err());
+    }
+
+    #[test]
+    fn test_allows() {
+        let all = Filter::new(1, &[]);
+        assert!(all.allows(Level::Debug, Some(5)));
+        assert!(all.allows(Level::Debug, None));
+        assert!(!all.allows(Level::Trace, Some(5)));
+
+        let some = Filter::new(2, &[5, 12]);
+        assert!(some.allows(Level::Trace, Some(12)));
+        assert!(!some.allows(Level::Debug, Some(8)));
+        assert!(!some.allows(Level::Debug, None));
+        assert!(!Filter::default().allows(Level::Debug, Some(5)));
+    }
+
+    #[test]
+    fn test_day_of() {
+        assert_eq!(day_of("aoc05"), Some(5));
+        assert_eq!(day_of("aoc12::tests"), Some(12));
+        assert_eq!(day_of("aoc_common::trace"), None);
+    }
+
+    #[test]
+    fn test_format_line() {
+        let line = format_line("aoc08::tests", 2, format_args!("cycle of {}", 3));
+        assert_eq!(line, "[aoc08]     cycle of 3");
+    }
+}
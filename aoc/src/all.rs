@@ -0,0 +1,127 @@
+use std::error::Error;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::Duration;
+
+use aoc_common::timed::{panic_message, solve_parts, Outcome, Status};
+use aoc_common::{input_hash, DynSolution, Format, Part, Source};
+
+use crate::{default_input, pool, registry};
+
+fn failed(day: u8, input_hash: &str, status: Status) -> Vec<Outcome> {
+    [1, 2]
+        .map(|part| Outcome {
+            day,
+            part,
+            answer: None,
+            elapsed: Duration::ZERO,
+            input_hash: input_hash.to_string(),
+            status: status.clone(),
+        })
+        .to_vec()
+}
+
+/// Parses and solves both parts of a day, turning errors and panics into
+/// statuses so that one failing part does not hide the other. Parsing is
+/// not subject to the timeout, solving is as described in [`solve_parts`].
+pub fn solve_day(
+    solution: &'static dyn DynSolution,
+    source: &Source,
+    timeout: Option<Duration>,
+) -> Vec<Outcome> {
+    let day = solution.day();
+    let input = match source.load(solution.example()) {
+        Ok(input) => input,
+        Err(err) => return failed(day, "", Status::Error(err.to_string())),
+    };
+    let hash = input_hash(&input);
+    let parsed = match panic::catch_unwind(AssertUnwindSafe(|| solution.parse(&input))) {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(err)) => return failed(day, &hash, Status::Error(err.to_string())),
+        Err(payload) => return failed(day, &hash, Status::Panicked(panic_message(&*payload))),
+    };
+    solve_parts(solution, parsed, &hash, Part::Both, timeout)
+}
+
+fn print_table(outcomes: &[Outcome]) {
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:>10}  status",
+        "day", "part", "answer", "time"
+    );
+    for outcome in outcomes {
+        println!(
+            "{:>3}  {:>4}  {:<20}  {:>10}  {}",
+            format!("{:02}", outcome.day),
+            outcome.part,
+            outcome.answer.as_deref().unwrap_or("-"),
+            format!("{:.1?}", outcome.elapsed),
+            outcome.status
+        );
+    }
+}
+
+/// Runs every registered day on at most `jobs` threads, then prints the
+/// outcomes in day order.
+pub fn run(
+    example: bool,
+    format: Format,
+    jobs: usize,
+    timeout: Option<Duration>,
+) -> Result<(), Box<dyn Error>> {
+    let results = pool::map(jobs, registry::DAYS.to_vec(), |solution| {
+        let source = Source::select(example, None, default_input(solution.day()));
+        solve_day(solution, &source, timeout)
+    });
+    let outcomes: Vec<Outcome> = registry::DAYS
+        .iter()
+        .zip(results)
+        .flat_map(|(solution, result)| {
+            // `solve_day` catches panics itself; this is only a backstop
+            result.unwrap_or_else(|payload| {
+                failed(
+                    solution.day(),
+                    "",
+                    Status::Panicked(panic_message(&*payload)),
+                )
+            })
+        })
+        .collect();
+
+    match format {
+        Format::Text => print_table(&outcomes),
+        Format::Json => {
+            for outcome in outcomes.iter() {
+                println!("{}", outcome.report().to_json());
+            }
+        }
+    }
+
+    let failures = outcomes.iter().filter(|o| o.status != Status::Ok).count();
+    if failures > 0 {
+        return Err(format!("{} part(s) failed", failures).into());
+    }
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_day_example() {
+        let outcomes = solve_day(registry::find(9).unwrap(), &Source::Example, None);
+        let answers: Vec<_> = outcomes.iter().map(|o| o.answer.as_deref()).collect();
+        assert_eq!(answers, [Some("114"), Some("2")]);
+        assert!(outcomes.iter().all(|o| o.status == Status::Ok));
+    }
+
+    #[test]
+    fn test_solve_day_missing_input() {
+        let source = Source::File(std::path::PathBuf::from("does/not/exist.in"));
+        let outcomes = solve_day(registry::find(9).unwrap(), &source, None);
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes
+            .iter()
+            .all(|o| matches!(o.status, Status::Error(_)) && o.answer.is_none()));
+    }
+}
@@ -0,0 +1,374 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use aoc_common::{Args, DynSolution, Source};
+use serde::{Deserialize, Serialize};
+
+use crate::{default_input, registry};
+
+const DEFAULT_ITERATIONS: usize = 10;
+const DEFAULT_THRESHOLD: f64 = 10.0;
+const DEFAULT_BASELINE: &str = "bench.json";
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub day: Option<u8>,
+    pub example: bool,
+    pub iterations: usize,
+    pub baseline: PathBuf,
+    pub save: bool,
+    /// Slowdown of the median, in percent, above which a timing is flagged.
+    pub threshold: f64,
+}
+
+fn parse_option<T: std::str::FromStr>(args: &Args, name: &str, default: T) -> Result<T, String> {
+    match args.option(name) {
+        Some(value) => value
+            .parse()
+            .map_err(|_| format!("invalid value `{}` for `{}`", value, name)),
+        None => Ok(default),
+    }
+}
+
+impl Options {
+    pub fn parse<I>(args: I) -> Result<Options, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let args = Args::parse(
+            args,
+            &["--example", "--save"],
+            &["--iterations", "--baseline", "--threshold"],
+        )?;
+        let day = match args.positional() {
+            [] => None,
+            [day] => Some(
+                day.parse::<u8>()
+                    .map_err(|_| format!("invalid day `{}`", day))?,
+            ),
+            _ => return Err("wrong number of arguments".to_string()),
+        };
+        let iterations = parse_option(&args, "--iterations", DEFAULT_ITERATIONS)?;
+        if iterations == 0 {
+            return Err("`--iterations` must be at least 1".to_string());
+        }
+
+        Ok(Options {
+            day,
+            example: args.flag("--example"),
+            iterations,
+            baseline: PathBuf::from(args.option("--baseline").unwrap_or(DEFAULT_BASELINE)),
+            save: args.flag("--save"),
+            threshold: parse_option(&args, "--threshold", DEFAULT_THRESHOLD)?,
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        };
+        f.pad(name)
+    }
+}
+
+/// Summary of the samples taken for one stage of one day, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timing {
+    pub day: u8,
+    pub stage: Stage,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub p95_ns: u64,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub timings: Vec<Timing>,
+}
+
+impl Baseline {
+    fn find(&self, day: u8, stage: Stage) -> Option<&Timing> {
+        self.timings
+            .iter()
+            .find(|t| t.day == day && t.stage == stage)
+    }
+
+    /// Adds `timing`, replacing any timing stored for the same day and stage.
+    fn record(&mut self, timing: Timing) {
+        self.timings
+            .retain(|t| !(t.day == timing.day && t.stage == timing.stage));
+        self.timings.push(timing);
+        self.timings.sort_by_key(|t| (t.day, t.stage));
+    }
+}
+
+// nearest-rank percentile of already sorted samples
+fn percentile(sorted: &[u64], p: usize) -> u64 {
+    let rank = (sorted.len() * p).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+impl Timing {
+    fn from_samples(day: u8, stage: Stage, samples: &[Duration]) -> Timing {
+        let mut sorted: Vec<u64> = samples.iter().map(|d| d.as_nanos() as u64).collect();
+        sorted.sort_unstable();
+        Timing {
+            day,
+            stage,
+            min_ns: sorted[0],
+            median_ns: percentile(&sorted, 50),
+            p95_ns: percentile(&sorted, 95),
+        }
+    }
+
+    /// Change of the median against `baseline`, in percent.
+    fn change(&self, baseline: &Timing) -> f64 {
+        (self.median_ns as f64 / baseline.median_ns.max(1) as f64 - 1.0) * 100.0
+    }
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Parses and solves `input` `iterations` times, timing every stage separately.
+pub fn bench_day(
+    solution: &dyn DynSolution,
+    input: &str,
+    iterations: usize,
+) -> Result<Vec<Timing>, Box<dyn Error>> {
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    for _ in 0..iterations {
+        let (parsed, elapsed) = time(|| solution.parse(input));
+        let parsed = parsed?;
+        samples[0].push(elapsed);
+        samples[1].push(time(|| solution.part1(parsed.as_ref())).1);
+        samples[2].push(time(|| solution.part2(parsed.as_ref())).1);
+    }
+
+    let stages = [Stage::Parse, Stage::Part1, Stage::Part2];
+    Ok(stages
+        .iter()
+        .zip(samples.iter())
+        .map(|(stage, samples)| Timing::from_samples(solution.day(), *stage, samples))
+        .collect())
+}
+
+// a missing file means there is no baseline yet, but any other failure is reported
+fn load_baseline(path: &Path) -> Result<Option<Baseline>, Box<dyn Error>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(serde_json::from_str(&text).map_err(|err| {
+            format!("invalid baseline `{}`: {}", path.display(), err)
+        })?)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(format!("cannot read baseline `{}`: {}", path.display(), err).into()),
+    }
+}
+
+fn format_ns(ns: u64) -> String {
+    format!("{:.1?}", Duration::from_nanos(ns))
+}
+
+pub fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let solutions: Vec<&dyn DynSolution> = match options.day {
+        Some(day) => {
+            vec![registry::find(day).ok_or_else(|| format!("day {} is not registered", day))?]
+        }
+        None => registry::DAYS.to_vec(),
+    };
+    let baseline = load_baseline(&options.baseline)?;
+
+    println!(
+        "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  baseline",
+        "day", "stage", "min", "median", "p95"
+    );
+    let mut timings = Vec::new();
+    let mut regressions = 0;
+    for solution in solutions {
+        let source = Source::select(options.example, None, default_input(solution.day()));
+        let input = source.load(solution.example())?;
+        for timing in bench_day(solution, &input, options.iterations)? {
+            let previous = baseline
+                .as_ref()
+                .and_then(|b| b.find(timing.day, timing.stage));
+            let change = match previous {
+                Some(previous) => {
+                    let change = timing.change(previous);
+                    if change > options.threshold {
+                        regressions += 1;
+                        format!("{:+.1}% REGRESSION", change)
+                    } else {
+                        format!("{:+.1}%", change)
+                    }
+                }
+                None => "-".to_string(),
+            };
+            println!(
+                "{:>3}  {:<5}  {:>10}  {:>10}  {:>10}  {}",
+                format!("{:02}", timing.day),
+                timing.stage,
+                format_ns(timing.min_ns),
+                format_ns(timing.median_ns),
+                format_ns(timing.p95_ns),
+                change
+            );
+            timings.push(timing);
+        }
+    }
+
+    if options.save {
+        // days that were not benched this time keep their saved timings
+        let mut baseline = baseline.unwrap_or_default();
+        for timing in timings {
+            baseline.record(timing);
+        }
+        fs::write(&options.baseline, serde_json::to_string_pretty(&baseline)?)?;
+        println!("saved baseline to `{}`", options.baseline.display());
+    }
+    if regressions > 0 {
+        return Err(format!(
+            "{} timing(s) regressed by more than {}%",
+            regressions, options.threshold
+        )
+        .into());
+    }
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Options, String> {
+        Options::parse(s.split_whitespace().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(
+            parse(""),
+            Ok(Options {
+                day: None,
+                example: false,
+                iterations: DEFAULT_ITERATIONS,
+                baseline: PathBuf::from(DEFAULT_BASELINE),
+                save: false,
+                threshold: DEFAULT_THRESHOLD,
+            })
+        );
+        assert_eq!(
+            parse("5 --example --iterations 3 --baseline b.json --save --threshold 2.5"),
+            Ok(Options {
+                day: Some(5),
+                example: true,
+                iterations: 3,
+                baseline: PathBuf::from("b.json"),
+                save: true,
+                threshold: 2.5,
+            })
+        );
+        assert!(parse("5 6").is_err());
+        assert!(parse("--iterations 0").is_err());
+        assert!(parse("--iterations x").is_err());
+    }
+
+    #[test]
+    fn test_timing_from_samples() {
+        let samples: Vec<Duration> = (1..=20).rev().map(Duration::from_nanos).collect();
+        let timing = Timing::from_samples(3, Stage::Part1, &samples);
+        assert_eq!(timing.min_ns, 1);
+        assert_eq!(timing.median_ns, 10);
+        assert_eq!(timing.p95_ns, 19);
+
+        let single = Timing::from_samples(3, Stage::Parse, &[Duration::from_nanos(7)]);
+        assert_eq!((single.min_ns, single.median_ns, single.p95_ns), (7, 7, 7));
+    }
+
+    #[test]
+    fn test_bench_day_and_baseline_roundtrip() {
+        let solution = registry::find(1).unwrap();
+        let timings = bench_day(solution, solution.example(), 3).unwrap();
+        let stages: Vec<Stage> = timings.iter().map(|t| t.stage).collect();
+        assert_eq!(stages, [Stage::Parse, Stage::Part1, Stage::Part2]);
+
+        let baseline = Baseline { timings };
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+        assert!(baseline.find(1, Stage::Part2).is_some());
+        assert!(baseline.find(2, Stage::Part2).is_none());
+    }
+
+    #[test]
+    fn test_baseline_record() {
+        let timing = |day, stage, median_ns| Timing {
+            day,
+            stage,
+            min_ns: 0,
+            median_ns,
+            p95_ns: 0,
+        };
+        let mut baseline = Baseline {
+            timings: vec![timing(2, Stage::Parse, 5), timing(2, Stage::Part1, 6)],
+        };
+        baseline.record(timing(1, Stage::Part2, 7));
+        baseline.record(timing(2, Stage::Parse, 8));
+        assert_eq!(
+            baseline.timings,
+            [
+                timing(1, Stage::Part2, 7),
+                timing(2, Stage::Parse, 8),
+                timing(2, Stage::Part1, 6)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_baseline() {
+        let dir = std::env::temp_dir().join(format!("aoc_bench_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert!(load_baseline(&dir.join("missing.json")).unwrap().is_none());
+
+        // a directory cannot be read, which is not the same as a missing file
+        let err = load_baseline(&dir).unwrap_err();
+        assert!(err.to_string().starts_with("cannot read baseline"));
+
+        let corrupt = dir.join("corrupt.json");
+        fs::write(&corrupt, "{").unwrap();
+        let err = load_baseline(&corrupt).unwrap_err();
+        assert!(err.to_string().starts_with("invalid baseline"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_change() {
+        let timing = |median_ns| Timing {
+            day: 1,
+            stage: Stage::Parse,
+            min_ns: 0,
+            median_ns,
+            p95_ns: 0,
+        };
+        assert_eq!(timing(150).change(&timing(100)), 50.0);
+        assert_eq!(timing(50).change(&timing(100)), -50.0);
+    }
+}
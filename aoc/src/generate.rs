@@ -0,0 +1,119 @@
+use std::error::Error;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use aoc_common::Args;
+
+use crate::registry;
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub day: u8,
+    pub seed: u64,
+    pub size: usize,
+    pub output: Option<PathBuf>,
+}
+
+impl Options {
+    pub fn parse<I>(args: I) -> Result<Options, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let args = Args::parse(args, &[], &["--seed", "--size", "--output"])?;
+        let day = match args.positional() {
+            [day] => day
+                .parse::<u8>()
+                .map_err(|_| format!("invalid day `{}`", day))?,
+            _ => return Err("wrong number of arguments".to_string()),
+        };
+        let seed = match args.option("--seed") {
+            Some(seed) => seed
+                .parse::<u64>()
+                .map_err(|_| format!("invalid seed `{}`", seed))?,
+            None => 0,
+        };
+        let size = match args.option("--size") {
+            Some(size) => size
+                .parse::<usize>()
+                .ok()
+                .filter(|size| *size > 0)
+                .ok_or_else(|| format!("invalid size `{}`", size))?,
+            None => 100,
+        };
+        Ok(Options {
+            day,
+            seed,
+            size,
+            output: args.option("--output").map(PathBuf::from),
+        })
+    }
+}
+
+pub fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let solution = registry::find(options.day)
+        .ok_or_else(|| format!("day {} is not registered", options.day))?;
+    let input = solution.generate(options.seed, options.size);
+    match &options.output {
+        Some(path) => fs::write(path, input)
+            .map_err(|err| format!("cannot write `{}`: {}", path.display(), err))?,
+        None => io::stdout().write_all(input.as_bytes())?,
+    }
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Options, String> {
+        Options::parse(s.split_whitespace().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("8"),
+            Ok(Options {
+                day: 8,
+                seed: 0,
+                size: 100,
+                output: None,
+            })
+        );
+        assert_eq!(
+            parse("12 --seed 7 --size 5 --output big.in"),
+            Ok(Options {
+                day: 12,
+                seed: 7,
+                size: 5,
+                output: Some(PathBuf::from("big.in")),
+            })
+        );
+        assert!(parse("").is_err());
+        assert!(parse("1 2").is_err());
+        assert!(parse("1 --seed -1").is_err());
+        assert!(parse("1 --size 0").is_err());
+    }
+
+    #[test]
+    fn test_generated_inputs_are_solvable() {
+        for solution in registry::DAYS {
+            for (seed, size) in [(0, 1), (1, 7), (2, 30)] {
+                let input = solution.generate(seed, size);
+                assert_eq!(
+                    input,
+                    solution.generate(seed, size),
+                    "day {}",
+                    solution.day()
+                );
+                let parsed = solution
+                    .parse(&input)
+                    .unwrap_or_else(|e| panic!("seed {}, size {}: {}\n{}", seed, size, e, input));
+                solution.part1(parsed.as_ref());
+                solution.part2(parsed.as_ref());
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+use std::env;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc_common::timed::parse_timeout;
+use aoc_common::trace::Filter;
+use aoc_common::{dispatch, Args, DynSolution, Format, Part, Source};
+
+mod all;
+mod bench;
+mod generate;
+mod new_day;
+mod pool;
+mod registry;
+mod verify;
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run {
+        day: u8,
+        part: Part,
+        source: Source,
+        format: Format,
+        timeout: Option<Duration>,
+        trace: Filter,
+    },
+    All {
+        example: bool,
+        format: Format,
+        jobs: usize,
+        timeout: Option<Duration>,
+        trace: Filter,
+    },
+    Bench(bench::Options),
+    Verify(verify::Options),
+    Generate(generate::Options),
+    NewDay(new_day::Options),
+}
+
+const USAGE: &str = "usage: aoc <day> [1|2|both] [INPUT | - | --example] [--format text|json]
+                [--timeout SECS] [-v | -vv] [--trace DAYS]
+       aoc --all [--example] [--format text|json] [--jobs N] [--timeout SECS]
+                 [-v | -vv] [--trace DAYS]
+       aoc bench [<day>] [--example] [--iterations N] [--baseline FILE] [--save] [--threshold PCT]
+       aoc verify [--example] [--manifest FILE] [--record]
+       aoc generate <day> [--seed N] [--size N] [--output FILE]
+       aoc new-day <day>";
+
+fn parse_args<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter().peekable();
+    match args
+        .next_if(|arg| ["bench", "verify", "generate", "new-day"].contains(&arg.as_str()))
+        .as_deref()
+    {
+        Some("bench") => return bench::Options::parse(args).map(Command::Bench),
+        Some("verify") => return verify::Options::parse(args).map(Command::Verify),
+        Some("generate") => return generate::Options::parse(args).map(Command::Generate),
+        Some("new-day") => return new_day::Options::parse(args).map(Command::NewDay),
+        _ => {}
+    }
+
+    let args = Args::parse(
+        args,
+        &["--all", "--example", "-v", "-vv"],
+        &["--format", "--jobs", "--timeout", "--trace"],
+    )?;
+    let example = args.flag("--example");
+    let format = match args.option("--format") {
+        Some(format) => format.parse::<Format>()?,
+        None => Format::Text,
+    };
+    let timeout = match args.option("--timeout") {
+        Some(secs) => Some(parse_timeout(secs)?),
+        None => None,
+    };
+    let trace = Filter::from_args(&args)?;
+
+    match (args.flag("--all"), args.positional()) {
+        (true, []) => {
+            let jobs = match args.option("--jobs") {
+                Some(jobs) => jobs
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|jobs| *jobs > 0)
+                    .ok_or_else(|| format!("invalid number of jobs `{}`", jobs))?,
+                None => pool::default_jobs(),
+            };
+            Ok(Command::All {
+                example,
+                format,
+                jobs,
+                timeout,
+                trace,
+            })
+        }
+        (false, [day, rest @ ..]) if rest.len() <= 2 => {
+            let day = day
+                .parse::<u8>()
+                .map_err(|_| format!("invalid day `{}`", day))?;
+            let part = match rest.first() {
+                Some(p) => p.parse::<Part>()?,
+                None => Part::Both,
+            };
+            let path = rest.get(1).map(|p| p.as_str());
+            let source = Source::select(example, path, default_input(day));
+            Ok(Command::Run {
+                day,
+                part,
+                source,
+                format,
+                timeout,
+                trace,
+            })
+        }
+        _ => Err("wrong number of arguments".to_string()),
+    }
+}
+
+fn default_input(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("aoc{:02}", day))
+        .join("src/input.in")
+}
+
+fn run_day(
+    solution: &'static dyn DynSolution,
+    part: Part,
+    source: &Source,
+    format: Format,
+    timeout: Option<Duration>,
+) -> Result<(), Box<dyn Error>> {
+    let input = source.load(solution.example())?;
+    dispatch(solution, part, &input, format, timeout)
+}
+
+fn run(command: Command) -> Result<(), Box<dyn Error>> {
+    match command {
+        Command::Run {
+            day,
+            part,
+            source,
+            format,
+            timeout,
+            trace,
+        } => {
+            trace.install();
+            let solution =
+                registry::find(day).ok_or_else(|| format!("day {} is not registered", day))?;
+            run_day(solution, part, &source, format, timeout)
+        }
+        Command::All {
+            example,
+            format,
+            jobs,
+            timeout,
+            trace,
+        } => {
+            trace.install();
+            all::run(example, format, jobs, timeout)
+        }
+        Command::Bench(options) => bench::run(&options),
+        Command::Verify(options) => verify::run(&options),
+        Command::Generate(options) => generate::run(&options),
+        Command::NewDay(options) => new_day::run(&options),
+    }
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("error: {}\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Command, String> {
+        parse_args(s.split_whitespace().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            parse("--all"),
+            Ok(Command::All {
+                example: false,
+                format: Format::Text,
+                jobs: pool::default_jobs(),
+                timeout: None,
+                trace: Filter::default(),
+            })
+        );
+        assert_eq!(
+            parse("--all --example --format json --jobs 3 --timeout 1.5"),
+            Ok(Command::All {
+                example: true,
+                format: Format::Json,
+                jobs: 3,
+                timeout: Some(Duration::from_millis(1500)),
+                trace: Filter::default(),
+            })
+        );
+        assert_eq!(
+            parse("5"),
+            Ok(Command::Run {
+                day: 5,
+                part: Part::Both,
+                source: Source::File(default_input(5)),
+                format: Format::Text,
+                timeout: None,
+                trace: Filter::default(),
+            })
+        );
+        assert_eq!(
+            parse("12 2 in.txt"),
+            Ok(Command::Run {
+                day: 12,
+                part: Part::Two,
+                source: Source::File(PathBuf::from("in.txt")),
+                format: Format::Text,
+                timeout: None,
+                trace: Filter::default(),
+            })
+        );
+        assert_eq!(
+            parse("3 1 -"),
+            Ok(Command::Run {
+                day: 3,
+                part: Part::One,
+                source: Source::Stdin,
+                format: Format::Text,
+                timeout: None,
+                trace: Filter::default(),
+            })
+        );
+        assert_eq!(
+            parse("3 --example --format=json"),
+            Ok(Command::Run {
+                day: 3,
+                part: Part::Both,
+                source: Source::Example,
+                format: Format::Json,
+                timeout: None,
+                trace: Filter::default(),
+            })
+        );
+        assert_eq!(
+            parse("5 -vv"),
+            Ok(Command::Run {
+                day: 5,
+                part: Part::Both,
+                source: Source::File(default_input(5)),
+                format: Format::Text,
+                timeout: None,
+                trace: Filter::new(2, &[]),
+            })
+        );
+        assert!(matches!(
+            parse("8 2 --timeout 2"),
+            Ok(Command::Run { timeout, .. }) if timeout == Some(Duration::from_secs(2))
+        ));
+        assert!(parse("8 --timeout 0").is_err());
+        assert!(matches!(
+            parse("--all -v --trace 8,12"),
+            Ok(Command::All { trace, .. }) if trace == Filter::new(1, &[8, 12])
+        ));
+        assert!(parse("--all --trace 26").is_err());
+        assert!(parse("").is_err());
+        assert!(parse("x").is_err());
+        assert!(parse("5 3").is_err());
+        assert!(parse("5 1 a b").is_err());
+        assert!(parse("--all 5").is_err());
+        assert!(parse("5 --format xml").is_err());
+        assert!(parse("--all --jobs 0").is_err());
+        assert!(parse("--all --timeout -1").is_err());
+        assert!(matches!(
+            parse("bench 5 --iterations 3"),
+            Ok(Command::Bench(bench::Options {
+                day: Some(5),
+                iterations: 3,
+                ..
+            }))
+        ));
+        assert!(parse("5 --iterations 3").is_err());
+        assert!(matches!(
+            parse("verify --record"),
+            Ok(Command::Verify(verify::Options { record: true, .. }))
+        ));
+        assert!(matches!(
+            parse("generate 10 --seed 3"),
+            Ok(Command::Generate(generate::Options {
+                day: 10,
+                seed: 3,
+                ..
+            }))
+        ));
+        assert_eq!(
+            parse("new-day 18"),
+            Ok(Command::NewDay(new_day::Options { day: 18 }))
+        );
+    }
+}
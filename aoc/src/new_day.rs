@@ -0,0 +1,250 @@
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aoc_common::Args;
+
+// the files of a new day's crate, relative to it, with `@NN@` standing for
+// the zero-padded day and `@DAY@` for the plain one
+const TEMPLATE: [(&str, &str); 7] = [
+    ("Cargo.toml", include_str!("../template/Cargo.toml.in")),
+    ("build.rs", include_str!("../template/build.rs.in")),
+    ("src/main.rs", include_str!("../template/main.rs.in")),
+    ("src/lib.rs", include_str!("../template/lib.rs.in")),
+    ("src/input.in", ""),
+    ("fixtures/example.txt", ""),
+    (
+        "fixtures/example.answers",
+        include_str!("../template/example.answers.in"),
+    ),
+];
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub day: u8,
+}
+
+impl Options {
+    pub fn parse<I>(args: I) -> Result<Options, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let args = Args::parse(args, &[], &[])?;
+        let day = match args.positional() {
+            [day] => day
+                .parse::<u8>()
+                .ok()
+                .filter(|day| (1..=25).contains(day))
+                .ok_or_else(|| format!("invalid day `{}`", day))?,
+            _ => return Err("wrong number of arguments".to_string()),
+        };
+        Ok(Options { day })
+    }
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn fill(template: &str, day: u8) -> String {
+    template
+        .replace("@NN@", &format!("{:02}", day))
+        .replace("@DAY@", &day.to_string())
+}
+
+// adds a day to the text of a workspace file
+type Edit = fn(&str, u8) -> Result<String, String>;
+
+// Inserts `entry` among the lines that `day_of` recognises as belonging to a
+// day, keeping them ordered by day.
+fn insert_line<F>(text: &str, entry: &str, day: u8, day_of: F) -> Result<String, String>
+where
+    F: Fn(&str) -> Option<u8>,
+{
+    let mut lines: Vec<&str> = text.lines().collect();
+    let days: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, day_of(line)?)))
+        .collect();
+    if days.iter().any(|&(_, d)| d == day) {
+        return Err(format!("day {} is already there", day));
+    }
+    let at = match days.iter().find(|&&(_, d)| d > day) {
+        Some(&(i, _)) => i,
+        None => days.last().ok_or("no other day to line up with")?.0 + 1,
+    };
+    lines.insert(at, entry);
+    Ok(lines.join("\n") + "\n")
+}
+
+fn add_member(manifest: &str, day: u8) -> Result<String, String> {
+    let entry = format!("    \"aoc{:02}\",", day);
+    insert_line(manifest, &entry, day, |line| {
+        line.trim()
+            .strip_prefix("\"aoc")?
+            .strip_suffix("\",")?
+            .parse()
+            .ok()
+    })
+}
+
+fn add_dependency(manifest: &str, day: u8) -> Result<String, String> {
+    let entry = format!("aoc{:02} = {{ path = \"../aoc{:02}\" }}", day, day);
+    insert_line(manifest, &entry, day, |line| {
+        line.strip_prefix("aoc")?.split_once(" = ")?.0.parse().ok()
+    })
+}
+
+fn register(registry: &str, day: u8) -> Result<String, String> {
+    let entry = format!("    &Erased::<aoc{:02}::Day{:02}>::new(),", day, day);
+    let registry = insert_line(registry, &entry, day, |line| {
+        line.trim()
+            .strip_prefix("&Erased::<aoc")?
+            .split_once("::")?
+            .0
+            .parse()
+            .ok()
+    })?;
+
+    // the array's length has to grow along with it
+    let prefix = "pub static DAYS: [&dyn DynSolution; ";
+    let start = registry.find(prefix).ok_or("cannot find `DAYS`")? + prefix.len();
+    let end = start + registry[start..].find(']').ok_or("cannot find `DAYS`")?;
+    let count: usize = registry[start..end]
+        .parse()
+        .map_err(|_| "cannot read the length of `DAYS`")?;
+    Ok(format!(
+        "{}{}{}",
+        &registry[..start],
+        count + 1,
+        &registry[end..]
+    ))
+}
+
+/// Creates the crate for `day` under `root` and adds it to the workspace
+/// and the runner. Nothing is written unless every edit applies. Returns
+/// the files created or changed.
+pub fn create(root: &Path, day: u8) -> Result<Vec<PathBuf>, Box<dyn Error>> {
+    let dir = root.join(format!("aoc{:02}", day));
+    if dir.exists() {
+        return Err(format!("`{}` already exists", dir.display()).into());
+    }
+
+    let registrations: [(&str, Edit); 3] = [
+        ("Cargo.toml", add_member),
+        ("aoc/Cargo.toml", add_dependency),
+        ("aoc/src/registry.rs", register),
+    ];
+    let mut edits: Vec<(PathBuf, String)> = Vec::new();
+    for (file, edit) in registrations {
+        let path = root.join(file);
+        let text = fs::read_to_string(&path)
+            .map_err(|err| format!("cannot read `{}`: {}", path.display(), err))?;
+        let text = edit(&text, day).map_err(|err| format!("`{}`: {}", path.display(), err))?;
+        edits.push((path, text));
+    }
+    for (file, template) in TEMPLATE {
+        edits.push((dir.join(file), fill(template, day)));
+    }
+
+    for (path, text) in &edits {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, text)
+            .map_err(|err| format!("cannot write `{}`: {}", path.display(), err))?;
+    }
+    Ok(edits.into_iter().map(|(path, _)| path).collect())
+}
+
+pub fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let root = workspace_root();
+    for path in create(&root, options.day)? {
+        println!(
+            "wrote {}",
+            path.strip_prefix(&root).unwrap_or(&path).display()
+        );
+    }
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn parse(s: &str) -> Result<Options, String> {
+        Options::parse(s.split_whitespace().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("18"), Ok(Options { day: 18 }));
+        assert!(parse("").is_err());
+        assert!(parse("0").is_err());
+        assert!(parse("26").is_err());
+        assert!(parse("18 19").is_err());
+    }
+
+    #[test]
+    fn test_insert_line() {
+        let manifest =
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc01\",\n    \"aoc03\",\n]\n";
+        assert_eq!(
+            add_member(manifest, 2).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc01\",\n    \"aoc02\",\n    \"aoc03\",\n]\n"
+        );
+        assert_eq!(
+            add_member(manifest, 4).unwrap(),
+            "[workspace]\nmembers = [\n    \"aoc\",\n    \"aoc01\",\n    \"aoc03\",\n    \"aoc04\",\n]\n"
+        );
+        assert!(add_member(manifest, 3).is_err());
+        assert!(add_member("[workspace]\n", 3).is_err());
+    }
+
+    #[test]
+    fn test_create() {
+        let root = env::temp_dir().join(format!("aoc_new_day_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        for file in ["Cargo.toml", "aoc/Cargo.toml", "aoc/src/registry.rs"] {
+            fs::copy(workspace_root().join(file), root.join(file)).unwrap();
+        }
+        let days = crate::registry::DAYS.len();
+        let day = crate::registry::DAYS.last().unwrap().day() + 1;
+        let name = format!("aoc{:02}", day);
+
+        let written = create(&root, day).unwrap();
+        assert_eq!(written.len(), 3 + TEMPLATE.len());
+
+        let read = |file: &str| fs::read_to_string(root.join(file)).unwrap();
+        assert!(read("Cargo.toml").contains(&format!("    \"{}\",\n]", name)));
+        assert!(
+            read("aoc/Cargo.toml").contains(&format!("{} = {{ path = \"../{}\" }}", name, name))
+        );
+        let registry = read("aoc/src/registry.rs");
+        assert!(registry.contains(&format!("; {}] = [", days + 1)));
+        assert!(registry.contains(&format!(
+            "::new(),\n    &Erased::<{}::Day{:02}>::new(),\n];",
+            name, day
+        )));
+
+        let lib = read(&format!("{}/src/lib.rs", name));
+        assert!(lib.contains(&format!("pub struct Day{:02};", day)));
+        assert!(lib.contains(&format!("const DAY: u8 = {};", day)));
+        assert!(!lib.contains('@'));
+        assert!(read(&format!("{}/Cargo.toml", name)).contains(&format!("name = \"{}\"", name)));
+
+        // nothing is touched when the day exists already
+        let manifest = read("Cargo.toml");
+        assert!(create(&root, day).is_err());
+        fs::remove_dir_all(root.join(&name)).unwrap();
+        assert!(create(&root, day).is_err());
+        assert_eq!(read("Cargo.toml"), manifest);
+        assert!(!root.join(&name).exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
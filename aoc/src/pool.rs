@@ -0,0 +1,84 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{mpsc, Mutex};
+use std::thread;
+
+/// The number of worker threads to use when none is given.
+pub fn default_jobs() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Applies `f` to every item on at most `jobs` worker threads and returns
+/// the results in the order of `items`. A panic in `f` is caught and
+/// returned as that item's error, so it does not affect the other items.
+pub fn map<T, R, F>(jobs: usize, items: Vec<T>, f: F) -> Vec<thread::Result<R>>
+where
+    T: Send,
+    R: Send,
+    F: Fn(T) -> R + Sync,
+{
+    let len = items.len();
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        let (queue, f) = (&queue, &f);
+        for _ in 0..jobs.clamp(1, len.max(1)) {
+            let tx = tx.clone();
+            scope.spawn(move || loop {
+                // the lock is released before `f` runs, so it is never poisoned
+                let next = queue.lock().unwrap().next();
+                let Some((i, item)) = next else {
+                    break;
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| f(item)));
+                tx.send((i, result))
+                    .expect("the receiver outlives the workers");
+            });
+        }
+    });
+    drop(tx);
+
+    let mut results: Vec<Option<thread::Result<R>>> = (0..len).map(|_| None).collect();
+    for (i, result) in rx {
+        results[i] = Some(result);
+    }
+    results
+        .into_iter()
+        .map(|result| result.expect("every item is processed"))
+        .collect()
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::timed::panic_message;
+
+    #[test]
+    fn test_map_keeps_order() {
+        let results = map(3, (0..20).collect(), |n: u64| n * n);
+        let squares: Vec<u64> = results.into_iter().map(|r| r.unwrap()).collect();
+        assert_eq!(squares, (0..20).map(|n| n * n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_map_isolates_panics() {
+        let results = map(2, vec![1, 0, 2], |n: u32| {
+            if n == 0 {
+                panic!("division by zero");
+            }
+            10 / n
+        });
+        assert_eq!(results[0].as_ref().unwrap(), &10);
+        assert_eq!(
+            panic_message(results[1].as_ref().unwrap_err().as_ref()),
+            "division by zero"
+        );
+        assert_eq!(results[2].as_ref().unwrap(), &5);
+    }
+
+    #[test]
+    fn test_map_empty() {
+        assert!(map(4, Vec::<u8>::new(), |n| n).is_empty());
+    }
+}
@@ -0,0 +1,47 @@
+use aoc_common::{DynSolution, Erased};
+
+pub static DAYS: [&dyn DynSolution; 17] = [
+    &Erased::<aoc01::Day01>::new(),
+    &Erased::<aoc02::Day02>::new(),
+    &Erased::<aoc03::Day03>::new(),
+    &Erased::<aoc04::Day04>::new(),
+    &Erased::<aoc05::Day05>::new(),
+    &Erased::<aoc06::Day06>::new(),
+    &Erased::<aoc07::Day07>::new(),
+    &Erased::<aoc08::Day08>::new(),
+    &Erased::<aoc09::Day09>::new(),
+    &Erased::<aoc10::Day10>::new(),
+    &Erased::<aoc11::Day11>::new(),
+    &Erased::<aoc12::Day12>::new(),
+    &Erased::<aoc13::Day13>::new(),
+    &Erased::<aoc14::Day14>::new(),
+    &Erased::<aoc15::Day15>::new(),
+    &Erased::<aoc16::Day16>::new(),
+    &Erased::<aoc17::Day17>::new(),
+];
+
+pub fn find(day: u8) -> Option<&'static dyn DynSolution> {
+    DAYS.iter().copied().find(|solution| solution.day() == day)
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days_are_ordered_and_unique() {
+        let days: Vec<u8> = DAYS.iter().map(|s| s.day()).collect();
+        let mut sorted = days.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(days, sorted);
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(5).map(|s| s.day()), Some(5));
+        assert!(find(0).is_none());
+        assert!(find(26).is_none());
+    }
+}
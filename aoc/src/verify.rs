@@ -0,0 +1,299 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use aoc_common::{input_hash, Args, DynSolution, Source};
+use serde::{Deserialize, Serialize};
+
+use crate::{default_input, registry};
+
+fn default_manifest() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("answers.json")
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub example: bool,
+    pub manifest: PathBuf,
+    pub record: bool,
+}
+
+impl Options {
+    pub fn parse<I>(args: I) -> Result<Options, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let args = Args::parse(args, &["--example", "--record"], &["--manifest"])?;
+        if !args.positional().is_empty() {
+            return Err("wrong number of arguments".to_string());
+        }
+        Ok(Options {
+            example: args.flag("--example"),
+            manifest: args
+                .option("--manifest")
+                .map(PathBuf::from)
+                .unwrap_or_else(default_manifest),
+            record: args.flag("--record"),
+        })
+    }
+}
+
+// ----------------------------------------------------------------------------
+/// The answers a day is known to give for one particular input.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Expected {
+    pub day: u8,
+    pub input_hash: String,
+    pub part1: String,
+    pub part2: String,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub answers: Vec<Expected>,
+}
+
+impl Manifest {
+    /// Loads the manifest at `path`, which is empty if the file does not exist.
+    pub fn load(path: &Path) -> Result<Manifest, Box<dyn Error>> {
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(serde_json::from_str(&text)
+                .map_err(|err| format!("invalid manifest `{}`: {}", path.display(), err))?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Default::default()),
+            Err(err) => Err(format!("cannot read manifest `{}`: {}", path.display(), err).into()),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    pub fn find(&self, day: u8, input_hash: &str) -> Option<&Expected> {
+        self.answers
+            .iter()
+            .find(|e| e.day == day && e.input_hash == input_hash)
+    }
+
+    /// Adds `expected`, replacing any answers stored for the same day and input.
+    pub fn record(&mut self, expected: Expected) {
+        self.answers
+            .retain(|e| !(e.day == expected.day && e.input_hash == expected.input_hash));
+        self.answers.push(expected);
+        self.answers
+            .sort_by(|a, b| (a.day, &a.input_hash).cmp(&(b.day, &b.input_hash)));
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Missing,
+    Recorded,
+    Error(String),
+}
+
+impl Status {
+    fn compare(answer: &str, expected: Option<&str>, record: bool) -> Status {
+        match (expected, record) {
+            (Some(expected), _) if expected == answer => Status::Pass,
+            (_, true) => Status::Recorded,
+            (Some(_), false) => Status::Fail,
+            (None, false) => Status::Missing,
+        }
+    }
+
+    fn is_ok(&self) -> bool {
+        matches!(self, Status::Pass | Status::Recorded)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Missing => write!(f, "missing"),
+            Status::Recorded => write!(f, "recorded"),
+            Status::Error(err) => write!(f, "error: {}", err),
+        }
+    }
+}
+
+/// Solves both parts of one day, returning the input hash and the answers.
+fn solve(
+    solution: &dyn DynSolution,
+    source: &Source,
+) -> Result<(String, [String; 2]), Box<dyn Error>> {
+    let input = source.load(solution.example())?;
+    let parsed = solution.parse(&input)?;
+    let answers = [
+        solution.part1(parsed.as_ref()).to_string(),
+        solution.part2(parsed.as_ref()).to_string(),
+    ];
+    Ok((input_hash(&input), answers))
+}
+
+fn print_row(day: u8, part: &str, answer: &str, expected: &str, status: &Status) {
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:<20}  {}",
+        format!("{:02}", day),
+        part,
+        answer,
+        expected,
+        status
+    );
+}
+
+pub fn run(options: &Options) -> Result<(), Box<dyn Error>> {
+    let mut manifest = Manifest::load(&options.manifest)?;
+    let mut failures = 0;
+
+    println!(
+        "{:>3}  {:>4}  {:<20}  {:<20}  status",
+        "day", "part", "answer", "expected"
+    );
+    for solution in registry::DAYS.iter() {
+        let day = solution.day();
+        let source = Source::select(options.example, None, default_input(day));
+        let (hash, answers) = match solve(*solution, &source) {
+            Ok(solved) => solved,
+            Err(err) => {
+                failures += 1;
+                print_row(day, "-", "-", "-", &Status::Error(err.to_string()));
+                continue;
+            }
+        };
+
+        let expected = manifest.find(day, &hash).cloned();
+        let expected = [
+            expected.as_ref().map(|e| e.part1.as_str()),
+            expected.as_ref().map(|e| e.part2.as_str()),
+        ];
+        for (i, (answer, expected)) in answers.iter().zip(expected).enumerate() {
+            let status = Status::compare(answer, expected, options.record);
+            if !status.is_ok() {
+                failures += 1;
+            }
+            print_row(
+                day,
+                &(i + 1).to_string(),
+                answer,
+                expected.unwrap_or("-"),
+                &status,
+            );
+        }
+
+        if options.record {
+            let [part1, part2] = answers;
+            manifest.record(Expected {
+                day,
+                input_hash: hash,
+                part1,
+                part2,
+            });
+        }
+    }
+
+    if options.record {
+        manifest.save(&options.manifest)?;
+        println!("recorded answers in `{}`", options.manifest.display());
+    }
+    if failures > 0 {
+        return Err(format!("{} answer(s) could not be verified", failures).into());
+    }
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Result<Options, String> {
+        Options::parse(s.split_whitespace().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn test_parse_options() {
+        assert_eq!(
+            parse(""),
+            Ok(Options {
+                example: false,
+                manifest: default_manifest(),
+                record: false,
+            })
+        );
+        assert_eq!(
+            parse("--example --record --manifest m.json"),
+            Ok(Options {
+                example: true,
+                manifest: PathBuf::from("m.json"),
+                record: true,
+            })
+        );
+        assert!(parse("5").is_err());
+    }
+
+    #[test]
+    fn test_status() {
+        assert_eq!(Status::compare("4", Some("4"), false), Status::Pass);
+        assert_eq!(Status::compare("4", Some("5"), false), Status::Fail);
+        assert_eq!(Status::compare("4", None, false), Status::Missing);
+        assert_eq!(Status::compare("4", Some("4"), true), Status::Pass);
+        assert_eq!(Status::compare("4", Some("5"), true), Status::Recorded);
+        assert_eq!(Status::compare("4", None, true), Status::Recorded);
+    }
+
+    #[test]
+    fn test_manifest_record() {
+        let expected = |day, hash: &str, part1: &str| Expected {
+            day,
+            input_hash: hash.to_string(),
+            part1: part1.to_string(),
+            part2: "0".to_string(),
+        };
+        let mut manifest: Manifest = Default::default();
+        manifest.record(expected(2, "b", "1"));
+        manifest.record(expected(1, "a", "1"));
+        manifest.record(expected(2, "b", "7"));
+        assert_eq!(
+            manifest.answers,
+            [expected(1, "a", "1"), expected(2, "b", "7")]
+        );
+        assert_eq!(manifest.find(2, "b").unwrap().part1, "7");
+        assert!(manifest.find(2, "a").is_none());
+    }
+
+    #[test]
+    fn test_manifest_load() {
+        let dir = std::env::temp_dir().join(format!("aoc_verify_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let missing = Manifest::load(&dir.join("missing.json")).unwrap();
+        assert!(missing.answers.is_empty());
+
+        // a directory cannot be read, which is not the same as a missing file
+        let err = Manifest::load(&dir).unwrap_err();
+        assert!(err.to_string().starts_with("cannot read manifest"));
+
+        let corrupt = dir.join("corrupt.json");
+        fs::write(&corrupt, "[").unwrap();
+        let err = Manifest::load(&corrupt).unwrap_err();
+        assert!(err.to_string().starts_with("invalid manifest"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_solve_example() {
+        let solution = registry::find(1).unwrap();
+        let (hash, answers) = solve(solution, &Source::Example).unwrap();
+        assert_eq!(hash, input_hash(solution.example()));
+        assert_eq!(answers, ["142", "142"]);
+    }
+}
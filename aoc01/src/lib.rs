@@ -0,0 +1,160 @@
+//! Day 1: Trebuchet?!
+//!
+//! Every line of the calibration document hides a value made of its first
+//! and last digit, written either as a digit or, in part 2, as a word.
+
+use aoc_common::{parse, ParseError, Rng, Solution};
+
+fn find_first_digit<I>(mut iterator: I) -> Option<char>
+where
+    I: DoubleEndedIterator<Item = char>,
+{
+    iterator.find(|item| item.is_ascii_digit())
+}
+
+fn find_last_digit<I>(iter: I) -> Option<char>
+where
+    I: DoubleEndedIterator<Item = char>,
+{
+    find_first_digit(iter.rev())
+}
+
+fn to_digit(c: char) -> i64 {
+    c as i64 - '0' as i64
+}
+
+// ----------------------------------------------------------------------------
+const MAPPING: [(&str, i64); 20] = [
+    ("zero", 0),
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+fn find_first_digit2(line: &str) -> Option<i64> {
+    MAPPING
+        .iter()
+        .filter_map(|(k, v)| line.find(k).map(|idx| (idx, *v)))
+        .min_by_key(|(idx, _)| *idx)
+        .map(|(_, v)| v)
+}
+
+fn find_last_digit2(line: &str) -> Option<i64> {
+    MAPPING
+        .iter()
+        .filter_map(|(k, v)| line.rfind(k).map(|idx| (idx, *v)))
+        .max_by_key(|(idx, _)| *idx)
+        .map(|(_, v)| v)
+}
+
+/// The calibration value of a line from its first and last digit, or `None`
+/// if it has no digits.
+pub fn calibration_value(line: &str) -> Option<i64> {
+    let first = find_first_digit(line.chars())?;
+    let last = find_last_digit(line.chars())?;
+    Some(to_digit(first) * 10 + to_digit(last))
+}
+
+/// Like [`calibration_value`], but digits may also be spelled out, as in
+/// `two1nine`.
+pub fn calibration_value_spelled(line: &str) -> Option<i64> {
+    Some(find_first_digit2(line)? * 10 + find_last_digit2(line)?)
+}
+
+// ----------------------------------------------------------------------------
+// lines of letters with digits and spelled-out digits mixed in; every line
+// gets at least one real digit, so that part 1 counts it too
+fn random_document(rng: &mut Rng, lines: usize) -> String {
+    let letters: Vec<char> = ('a'..='z').collect();
+    let mut document = String::new();
+    for _ in 0..lines {
+        let mut pieces: Vec<String> = (0..rng.index(1..6))
+            .map(|_| match rng.below(3) {
+                0 => MAPPING[rng.index(1..10)].0.to_string(),
+                1 => rng.range(1..10).to_string(),
+                _ => (0..rng.index(1..6))
+                    .map(|_| *rng.choose(&letters))
+                    .collect(),
+            })
+            .collect();
+        let at = rng.index(0..pieces.len() + 1);
+        pieces.insert(at, rng.range(1..10).to_string());
+        document += &pieces.concat();
+        document.push('\n');
+    }
+    document
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the document into its lines.
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Vec<String>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::records(Self::DAY, input)
+            .iter()
+            .map(|line| match find_first_digit2(line.text) {
+                Some(_) => Ok(line.text.to_string()),
+                None => Err(line.error(line.text, "a digit or spelled-out digit")),
+            })
+            .collect()
+    }
+
+    /// Lines whose digits are all spelled out have no part 1 value and are skipped.
+    fn part1(lines: &Self::Input) -> i64 {
+        lines
+            .iter()
+            .filter_map(|line| calibration_value(line))
+            .sum()
+    }
+
+    fn part2(lines: &Self::Input) -> i64 {
+        lines
+            .iter()
+            .map(|line| calibration_value_spelled(line).expect("parse checks for a digit"))
+            .sum()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_document(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day01);
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day01::parse("1abc2\nabc\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(1, 2, 1, "a digit or spelled-out digit", "`abc`")
+        );
+    }
+}
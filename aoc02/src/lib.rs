@@ -0,0 +1,204 @@
+//! Day 2: Cube Conundrum
+//!
+//! Each game is a few draws of red, green and blue cubes from a bag. Part 1
+//! asks which games fit a bag of 12 red, 13 green and 14 blue cubes, part 2
+//! for the smallest bag each game needs.
+
+use std::cmp::max;
+
+use aoc_common::parse::{self, Line};
+use aoc_common::{ParseError, Rng, Solution};
+
+/// A number of cubes of each colour.
+#[derive(Default, PartialEq, Debug)]
+pub struct Marbles {
+    pub red: i32,
+    pub green: i32,
+    pub blue: i32,
+}
+
+/// A game and the cubes shown in each of its draws.
+#[derive(PartialEq, Debug)]
+pub struct Game {
+    pub id: i32,
+    pub draws: Vec<Marbles>,
+}
+
+impl Game {
+    /// Whether every draw fits a bag of 12 red, 13 green and 14 blue cubes.
+    pub fn is_possible(&self) -> bool {
+        self.draws
+            .iter()
+            .all(|d| d.red <= 12 && d.green <= 13 && d.blue <= 14)
+    }
+
+    /// The fewest cubes of each colour that make every draw possible.
+    pub fn min_marbles(&self) -> Marbles {
+        let mut mmarb: Marbles = Default::default();
+        for m in self.draws.iter() {
+            mmarb.red = max(mmarb.red, m.red);
+            mmarb.green = max(mmarb.green, m.green);
+            mmarb.blue = max(mmarb.blue, m.blue);
+        }
+        mmarb
+    }
+
+    /// The product of the [`min_marbles`](Game::min_marbles) counts.
+    pub fn game_power(&self) -> i32 {
+        let mmarb = self.min_marbles();
+        mmarb.red * mmarb.green * mmarb.blue
+    }
+}
+
+fn parse_marbles(line: &Line, marble_draw: &str) -> Result<Marbles, ParseError> {
+    let mut marbles: Marbles = Default::default();
+    for draw in marble_draw.split(',') {
+        let mut marble = draw.split_whitespace();
+        let n = line.parse(marble.next().unwrap_or(draw), "a marble count")?;
+        match marble.next() {
+            Some("red") => marbles.red = n,
+            Some("green") => marbles.green = n,
+            Some("blue") => marbles.blue = n,
+            Some(colour) => return Err(line.error(colour, "`red`, `green` or `blue`")),
+            None => return Err(line.error(&draw[draw.len()..], "a marble colour")),
+        }
+    }
+    Ok(marbles)
+}
+
+fn parse_line(line: &Line) -> Result<Game, ParseError> {
+    let (name, draws) = line.split_once(line.text, ':')?;
+    let id = match name.strip_prefix("Game ") {
+        Some(id) => line.parse(id, "a game id")?,
+        None => return Err(line.error(name, "`Game <id>`")),
+    };
+
+    Ok(Game {
+        id,
+        draws: draws
+            .split(';')
+            .map(|draw| parse_marbles(line, draw))
+            .collect::<Result<_, _>>()?,
+    })
+}
+
+// ----------------------------------------------------------------------------
+fn random_games(rng: &mut Rng, games: usize) -> String {
+    let mut record = String::new();
+    for id in 1..=games {
+        let draws: Vec<String> = (0..rng.index(1..7))
+            .map(|_| {
+                let mut colours = ["red", "green", "blue"];
+                rng.shuffle(&mut colours);
+                let shown = rng.index(1..4);
+                let marbles: Vec<String> = colours[..shown]
+                    .iter()
+                    .map(|colour| format!("{} {}", rng.range(1..21), colour))
+                    .collect();
+                marbles.join(", ")
+            })
+            .collect();
+        record += &format!("Game {}: {}\n", id, draws.join("; "));
+    }
+    record
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the record into [`Game`]s.
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Vec<Game>;
+    type Answer = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::records(Self::DAY, input)
+            .iter()
+            .map(parse_line)
+            .collect()
+    }
+
+    fn part1(games: &Self::Input) -> i32 {
+        games.iter().filter(|g| g.is_possible()).map(|g| g.id).sum()
+    }
+
+    fn part2(games: &Self::Input) -> i32 {
+        games.iter().map(|g| g.game_power()).sum()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_games(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day02);
+
+    #[test]
+    fn test_parsing() {
+        let game = parse_line(
+            &parse::lines(2, "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")[0],
+        )
+        .unwrap();
+        assert_eq!(game.id, 1);
+        assert_eq!(
+            game.draws,
+            [
+                Marbles {
+                    red: 4,
+                    green: 0,
+                    blue: 3
+                },
+                Marbles {
+                    red: 1,
+                    green: 2,
+                    blue: 6
+                },
+                Marbles {
+                    red: 0,
+                    green: 2,
+                    blue: 0
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_min_marbles() {
+        let game = parse_line(
+            &parse::lines(2, "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")[0],
+        )
+        .unwrap();
+        assert_eq!(game.id, 1);
+        assert_eq!(
+            game.min_marbles(),
+            Marbles {
+                red: 4,
+                green: 2,
+                blue: 6
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day02::parse("Game 1: 3 blue\nGame x: 1 red\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 2, 6, "a game id", "`x`"));
+
+        let err = Day02::parse("Game 1: 3 blue, 4 pink\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, 19, "`red`, `green` or `blue`", "`pink`")
+        );
+
+        let err = Day02::parse("Game 1 3 blue\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 1, 14, "`:`", "end of line"));
+    }
+}
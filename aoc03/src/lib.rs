@@ -0,0 +1,245 @@
+//! Day 3: Gear Ratios
+//!
+//! The engine schematic is a grid of numbers and symbols. A number next to
+//! a symbol is a part number, and a `*` next to exactly two part numbers is
+//! a gear whose ratio is their product.
+
+use std::collections::HashSet;
+
+use aoc_common::parse::{self, Line};
+use aoc_common::{Grid, ParseError, Rng, Solution};
+
+const TILES: &str = "0123456789.!\"#$%&'()*+,-/:;<=>?@[\\]^_`{|}~";
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Number {
+    val: u64,
+    is_part: bool,
+}
+
+/// The part numbers and gears of an engine schematic.
+#[derive(Debug)]
+pub struct Schematic {
+    numbers: Vec<Number>,
+    gears: Vec<u128>,
+    grid: Grid<usize>, // mapping from the coordinates to the index in numbers
+}
+
+impl Schematic {
+    fn new(tiles: &Grid<char>) -> Schematic {
+        let mut schematic = Schematic {
+            numbers: vec![Default::default()],
+            gears: vec![],
+            grid: Grid::new(tiles.width(), tiles.height(), 0),
+        };
+        schematic.parse_input(tiles);
+        schematic
+    }
+
+    /// The numbers next to a symbol, in reading order.
+    pub fn part_numbers(&self) -> impl Iterator<Item = u64> + '_ {
+        self.numbers.iter().filter(|n| n.is_part).map(|n| n.val)
+    }
+
+    /// The ratio of every gear, in reading order. The product of two 64-bit
+    /// part numbers always fits in a `u128`.
+    pub fn gear_ratios(&self) -> &[u128] {
+        &self.gears
+    }
+
+    fn parse_input(&mut self, tiles: &Grid<char>) {
+        for (row, line) in tiles.rows().enumerate() {
+            self.parse_line(row, line);
+        }
+        for (row, line) in tiles.rows().enumerate() {
+            self.mark_parts_in_line(row, line);
+        }
+    }
+
+    fn parse_line(&mut self, row: usize, line: &[char]) {
+        let mut curr_num: Option<Number> = None;
+
+        // fill out numbers
+        for (col, &c) in line.iter().enumerate() {
+            if c.is_numeric() {
+                let n = c as u64 - '0' as u64;
+                let num = curr_num.get_or_insert_with(Default::default);
+                num.val = num.val * 10 + n;
+                self.grid[(row, col)] = self.numbers.len();
+            } else if let Some(num) = curr_num.take() {
+                self.numbers.push(num);
+            }
+        }
+        self.numbers.extend(curr_num);
+    }
+
+    fn mark_parts_in_line(&mut self, row: usize, line: &[char]) {
+        for (col, &c) in line.iter().enumerate() {
+            if !c.is_numeric() && c != '.' {
+                let num_parts = self.mark_parts(row, col, |n| n.is_part = true);
+                if c == '*' && num_parts == 2 {
+                    let mut gear: u128 = 1;
+                    self.mark_parts(row, col, |n| gear *= n.val as u128);
+                    self.gears.push(gear);
+                }
+            }
+        }
+    }
+
+    fn mark_parts<F>(&mut self, row: usize, col: usize, mut func: F) -> usize
+    where
+        F: FnMut(&mut Number),
+    {
+        let mut parts: HashSet<usize> = HashSet::new();
+
+        for pos in self.grid.neighbours8((row, col)) {
+            let index = self.grid[pos];
+            // index 0 stands for no number
+            if index > 0 && parts.insert(index) {
+                func(&mut self.numbers[index]);
+            }
+        }
+
+        parts.len()
+    }
+}
+
+// every part number must fit in a `u64`, which keeps the gear ratios exact
+fn check_numbers(rows: &[Line]) -> Result<(), ParseError> {
+    for row in rows {
+        for number in row.text.split(|c: char| !c.is_ascii_digit()) {
+            if !number.is_empty() {
+                row.parse::<u64>(number, "a part number that fits in 64 bits")?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// ----------------------------------------------------------------------------
+// a square schematic with numbers kept apart by at least one `.`, and symbols
+// sprinkled over the remaining space
+fn random_schematic(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(3);
+    let mut tiles = Grid::new(size, size, '.');
+    for row in 0..size {
+        let mut col = rng.index(0..4);
+        while col < size {
+            let number = rng.range(1..1000).to_string();
+            if col + number.len() > size {
+                break;
+            }
+            for (i, digit) in number.chars().enumerate() {
+                tiles[(row, col + i)] = digit;
+            }
+            col += number.len() + rng.index(1..8);
+        }
+    }
+    let symbols: Vec<char> = "*#+$/@=%&-".chars().collect();
+    for pos in tiles.positions().collect::<Vec<_>>() {
+        if tiles[pos] == '.' && rng.chance(1, 6) {
+            tiles[pos] = if rng.chance(1, 2) {
+                '*'
+            } else {
+                *rng.choose(&symbols)
+            };
+        }
+    }
+    tiles.to_string()
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the grid into a [`Schematic`].
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Schematic;
+    type Answer = Option<u128>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let tiles = Grid::parse(Self::DAY, input, TILES)?;
+        check_numbers(&parse::records(Self::DAY, input))?;
+        Ok(Schematic::new(&tiles))
+    }
+
+    fn part1(schematic: &Self::Input) -> Option<u128> {
+        // there are far fewer than 2^64 numbers, so the sum cannot overflow
+        Some(schematic.part_numbers().map(u128::from).sum())
+    }
+
+    fn part2(schematic: &Self::Input) -> Option<u128> {
+        schematic
+            .gear_ratios()
+            .iter()
+            .try_fold(0u128, |total, &ratio| total.checked_add(ratio))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_schematic(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day03);
+
+    #[test]
+    fn test_parsing_numbers() {
+        let schematic = Day03::parse(Day03::EXAMPLE).unwrap();
+        let nums: u64 = schematic.numbers.iter().map(|n| n.val).sum();
+        assert_eq!(nums, 4533);
+    }
+
+    #[test]
+    fn test_generate_large() {
+        // the gear ratios of a schematic this size add up to more than an `i32` holds
+        for seed in [1, 7] {
+            let schematic = Day03::parse(&random_schematic(&mut Rng::new(seed), 1000)).unwrap();
+            assert!(Day03::part1(&schematic) > Some(0));
+            assert!(Day03::part2(&schematic) > Some(i32::MAX as u128));
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day03::parse("467..\n...*..\n").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 2, 6, "a row of width 5", "`.`"));
+
+        let err = Day03::parse("..*...................\n.123456789012345678901\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                3,
+                2,
+                2,
+                "a part number that fits in 64 bits",
+                "`123456789012345678901`"
+            )
+        );
+    }
+
+    #[test]
+    fn test_long_numbers() {
+        let schematic = Day03::parse("12345678901*.\n..........2..\n").unwrap();
+        assert_eq!(Day03::part1(&schematic), Some(12345678903));
+        assert_eq!(Day03::part2(&schematic), Some(24691357802));
+
+        // a gear ratio beyond 64 bits, and a sum of two beyond 128 bits
+        let max = u64::MAX.to_string();
+        let dots = ".".repeat(max.len());
+        let input = format!("{m}*{m}*{m}\n{d}.{d}.{d}\n", m = max, d = dots);
+        let schematic = Day03::parse(&input).unwrap();
+        assert_eq!(Day03::part1(&schematic), Some(3 * u64::MAX as u128));
+        assert_eq!(
+            schematic.gear_ratios(),
+            [u64::MAX as u128 * u64::MAX as u128; 2]
+        );
+        assert_eq!(Day03::part2(&schematic), None);
+    }
+}
@@ -1,199 +1,7 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
-use std::collections::HashSet;
+use std::process::ExitCode;
 
-#[derive(Default, Clone, Copy)]
-struct Number {
-    val: i32,
-    is_part: bool,
-}
-
-struct Schematic {
-    numbers: Vec<Number>,
-    gears: Vec<i32>,
-    grid: Vec<Vec<usize>>, // mapping from the coordinates to the index in numbers
-}
-
-impl Schematic {
-    fn new(lines: &[String]) -> Schematic {
-        let mut schematic = Schematic {
-            numbers: vec![Default::default()],
-            gears: vec![],
-            grid: vec![vec![0; lines[0].len()]; lines.len()],
-        };
-        schematic.parse_input(lines);
-        schematic
-    }
-
-    fn parse_input(&mut self, lines: &[String]) {
-        for (row, line) in lines.iter().enumerate() {
-            self.parse_line(row, line);
-        }
-        for (row, line) in lines.iter().enumerate() {
-            self.mark_parts_in_line(row, line);
-        }
-    }
-
-    fn parse_line(&mut self, row: usize, line: &str) {
-        let mut curr_num: Number = Default::default();
-
-        // fill out numbers
-        for (col, c) in line.chars().enumerate() {
-            if c.is_numeric() {
-                let n = c as i32 - '0' as i32;
-                curr_num.val = curr_num.val * 10 + n;
-                self.grid[row][col] = self.numbers.len();
-            } else {
-                curr_num = self.record_new_num(curr_num);
-            }
-        }
-        self.record_new_num(curr_num);
-    }
-
-    fn mark_parts_in_line(&mut self, row: usize, line: &str) {
-        for (col, c) in line.chars().enumerate() {
-            if !c.is_numeric() && c != '.' {
-                let num_parts = self.mark_parts(row, col, |n| n.is_part = true);
-                if c == '*' && num_parts == 2 {
-                    let mut gear: i32 = 1;
-                    self.mark_parts(row, col, |n| gear *= n.val);
-                    self.gears.push(gear);
-                }
-            }
-        }
-    }
-
-    fn record_new_num(&mut self, num: Number) -> Number {
-        if num.val != 0 {
-            self.numbers.push(num);
-            Default::default()
-        } else {
-            num
-        }
-    }
-
-    fn mark_parts<F>(&mut self, row: usize, col: usize, mut func: F) -> usize
-    where
-        F: FnMut(&mut Number),
-    {
-        let offsets = [-1, 0, 1];
-        let mut parts: HashSet<*const Number> = HashSet::new();
-
-        for &di in &offsets {
-            for &dj in &offsets {
-                if let (Some(i), Some(j)) = (row.checked_add_signed(di), col.checked_add_signed(dj))
-                {
-                    if i < self.grid.len()
-                        && j < self.grid[i].len()
-                        && self.numbers[self.grid[i][j]].val > 0
-                        && parts.insert((&self.numbers[self.grid[i][j]]) as *const Number)
-                    {
-                        func(&mut self.numbers[self.grid[i][j]]);
-                    }
-                }
-            }  
-        }
-
-        parts.len()
-    }
-}
-
-fn solve(lines: &[String]) -> i32 {
-    let schematic = Schematic::new(lines);
-
-    schematic
-        .numbers
-        .iter()
-        .filter(|n| n.is_part)
-        .map(|n| n.val)
-        .sum()
-}
-
-// ----------------------------------------------------------------------------
-fn solve2(lines: &[String]) -> i32 {
-    let schematic = Schematic::new(lines);
-
-    schematic.gears.iter().sum()
-}
-
-// ----------------------------------------------------------------------------
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parsing_numbers() {
-        let input = r"467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..
-";
-        let lines: Vec<String> = input.lines().map(|line| line.to_string()).collect();
-        let schematic = Schematic::new(&lines);
-        let nums: i32 = schematic.numbers.iter().map(|n| n.val).sum();
-        assert_eq!(nums, 4533);
-    }
-
-    #[test]
-    fn test_solution1() {
-        let input = r"467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..
-";
-        let lines: Vec<String> = input.lines().map(|line| line.to_string()).collect();
-        let result = solve(&lines);
-        assert_eq!(result, 4361);
-    }
-
-    #[test]
-    fn test_solution2() {
-        let input = r"467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..
-";
-        let lines: Vec<String> = input.lines().map(|line| line.to_string()).collect();
-        let result = solve2(&lines);
-        assert_eq!(result, 467835);
-    }
-}
-
-// ----------------------------------------------------------------------------
-fn read_lines<P>(filename: P) -> io::Result<Vec<String>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    let buf_reader = io::BufReader::new(file);
-    buf_reader.lines().collect()
-}
-
-fn main() -> io::Result<()> {
-    let filename = "src/input.in";
-    let lines = read_lines(filename)?;
-
-    println!("Answer, part 1: {}", solve(&lines));
-    println!("Answer, part 2: {}", solve2(&lines));
+use aoc03::Day03;
 
-    Ok(())
+fn main() -> ExitCode {
+    aoc_common::run::<Day03>(concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.in"))
 }
@@ -0,0 +1,181 @@
+//! Day 4: Scratchcards
+//!
+//! Every card has some winning numbers and the numbers you have. Part 1
+//! scores cards by their matches, part 2 lets matches win copies of the
+//! cards below.
+
+use std::collections::HashSet;
+
+use aoc_common::parse::{self, Line};
+use aoc_common::{ParseError, Rng, Solution};
+
+/// A scratchcard.
+#[derive(Debug, PartialEq)]
+pub struct Card {
+    pub id: i32,
+    pub winning: HashSet<i32>,
+    pub yours: HashSet<i32>,
+}
+
+fn parse_card(line: &Line) -> Result<Card, ParseError> {
+    let (name, numbers) = line.split_once(line.text, ':')?;
+    let id = match name.strip_prefix("Card") {
+        Some(id) => line.parse(id.trim_start(), "a card id")?,
+        None => return Err(line.error(name, "`Card <id>`")),
+    };
+    let (winning, yours) = line.split_once(numbers, '|')?;
+    Ok(Card {
+        id,
+        winning: line.numbers(winning, "a number")?.into_iter().collect(),
+        yours: line.numbers(yours, "a number")?.into_iter().collect(),
+    })
+}
+
+/// How many of your numbers are winning numbers.
+pub fn num_matches(card: &Card) -> usize {
+    card.winning.intersection(&card.yours).count()
+}
+
+/// The points a card is worth: one for the first match, doubled for each
+/// further match. `None` if that does not fit in a `u64`.
+pub fn points(card: &Card) -> Option<u64> {
+    match num_matches(card) {
+        0 => Some(0),
+        matches => 1u64.checked_shl(u32::try_from(matches - 1).ok()?),
+    }
+}
+
+/// How many cards you end up with when every card wins a copy of as many
+/// cards below it as it has matches. `None` if that does not fit in a `u64`.
+///
+/// The table must not let a card win copies of cards past its end, which
+/// [`Day04::parse`] checks.
+pub fn total_cards(cards: &[Card]) -> Option<u64> {
+    let mut copies: Vec<u64> = vec![1; cards.len()];
+    for (i, win) in cards.iter().map(num_matches).enumerate() {
+        if copies[i] == 0 && win == 0 {
+            break;
+        }
+        for j in i + 1..i + win + 1 {
+            copies[j] = copies[j].checked_add(copies[i])?;
+        }
+    }
+    copies
+        .iter()
+        .try_fold(0u64, |total, &n| total.checked_add(n))
+}
+
+// ----------------------------------------------------------------------------
+// a card never wins copies of cards past the end of the table
+fn random_cards(rng: &mut Rng, cards: usize) -> String {
+    let mut table = String::new();
+    for id in 1..=cards {
+        let mut numbers: Vec<u64> = (1..100).collect();
+        rng.shuffle(&mut numbers);
+        let (winning, rest) = numbers.split_at(10);
+        let matches = rng.index(0..11.min(cards - id + 1));
+        let mut yours: Vec<u64> = winning[..matches].to_vec();
+        yours.extend_from_slice(&rest[..25 - matches]);
+        rng.shuffle(&mut yours);
+
+        let column = |numbers: &[u64]| {
+            let numbers: Vec<String> = numbers.iter().map(|n| format!("{:>2}", n)).collect();
+            numbers.join(" ")
+        };
+        table += &format!("Card {:>3}: {} | {}\n", id, column(winning), column(&yours));
+    }
+    table
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the table into [`Card`]s.
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Vec<Card>;
+    type Answer = Option<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let lines = parse::records(Self::DAY, input);
+        let cards = lines
+            .iter()
+            .map(parse_card)
+            .collect::<Result<Vec<_>, _>>()?;
+        // no card may win copies of cards past the end of the table
+        for (i, (line, card)) in lines.iter().zip(&cards).enumerate() {
+            let below = cards.len() - i - 1;
+            if num_matches(card) > below {
+                let (name, _) = line.split_once(line.text, ':')?;
+                return Err(line.error(name, &format!("a card with at most {} matches", below)));
+            }
+        }
+        Ok(cards)
+    }
+
+    fn part1(cards: &Self::Input) -> Option<u64> {
+        cards
+            .iter()
+            .try_fold(0u64, |total, card| total.checked_add(points(card)?))
+    }
+
+    fn part2(cards: &Self::Input) -> Option<u64> {
+        total_cards(cards)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_cards(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day04);
+
+    #[test]
+    fn test_parse_card() {
+        let input = r"Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
+        let card = parse_card(&parse::lines(4, input)[0]).unwrap();
+        assert_eq!(
+            card,
+            Card {
+                id: 1,
+                winning: [41, 48, 83, 86, 17].iter().cloned().collect(),
+                yours: [83, 86, 6, 31, 17, 9, 48, 53].iter().cloned().collect(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day04::parse("Card 1: 41 48 | 83 x6\n").unwrap_err();
+        assert_eq!(err, ParseError::new(4, 1, 20, "a number", "`x6`"));
+
+        let err = Day04::parse("Card 1: 41 48 83 86\n").unwrap_err();
+        assert_eq!(err, ParseError::new(4, 1, 20, "`|`", "end of line"));
+
+        let err = Day04::parse("Card 1: 41 48 | 41 9\nCard 2: 41 48 | 41 48\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(4, 2, 1, "a card with at most 0 matches", "`Card 2`")
+        );
+    }
+
+    #[test]
+    fn test_points_overflow() {
+        let numbers: HashSet<i32> = (1..=65).collect();
+        let card = |n| Card {
+            id: 1,
+            winning: numbers.iter().cloned().filter(|&x| x <= n).collect(),
+            yours: numbers.clone(),
+        };
+        assert_eq!(points(&card(64)), Some(1 << 63));
+        assert_eq!(points(&card(65)), None);
+        assert_eq!(Day04::part1(&vec![card(64), card(64)]), None);
+    }
+}
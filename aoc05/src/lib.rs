@@ -0,0 +1,433 @@
+//! Day 5: If You Give A Seed A Fertilizer
+//!
+//! The almanac maps seeds through soil, fertilizer and so on to locations.
+//! Each map is stored as a sorted set of breakpoints: a key starts a stretch
+//! of numbers that is shifted to start at its value instead, up to the next
+//! key. Part 2 pushes whole ranges of seeds through the maps at once.
+
+use std::collections::BTreeMap;
+use std::ops::Bound::{Excluded, Included, Unbounded};
+
+use aoc_common::parse::{self, Line, Sections};
+use aoc_common::{debug, ParseError, Rng, Solution};
+
+/// A seed and every number it maps to on the way to its location.
+#[derive(Debug, PartialEq, Default)]
+pub struct Seed {
+    pub seed: i64,
+    pub soil: i64,
+    pub fert: i64,
+    pub water: i64,
+    pub light: i64,
+    pub temp: i64,
+    pub humid: i64,
+    pub loc: i64,
+}
+
+/// The seven maps of the almanac, as breakpoints.
+#[derive(Debug, Default)]
+pub struct Mappings {
+    pub seed2soil: BTreeMap<i64, i64>,
+    pub soil2fert: BTreeMap<i64, i64>,
+    pub fert2water: BTreeMap<i64, i64>,
+    pub water2light: BTreeMap<i64, i64>,
+    pub light2temp: BTreeMap<i64, i64>,
+    pub temp2humid: BTreeMap<i64, i64>,
+    pub humid2loc: BTreeMap<i64, i64>,
+}
+
+/// The seeds line, read as single seeds or as `start length` pairs, and the
+/// maps.
+#[derive(Debug)]
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    pub mappings: Mappings,
+}
+
+/// Maps one number through a map.
+pub fn translate(mapping: &BTreeMap<i64, i64>, el: &i64) -> i64 {
+    let mut upper_bound = mapping.range((Unbounded, Included(el)));
+
+    match upper_bound.next_back() {
+        Some((from, to)) => to + el - from,
+        None => *el,
+    }
+}
+
+fn parse_seeds(line: &Line) -> Result<Vec<i64>, ParseError> {
+    let seeds: Vec<i64> = line.key_values("seeds", "a seed number")?;
+    if seeds.is_empty() || seeds.len() % 2 == 1 {
+        return Err(line.error_at_end("a seed range length"));
+    }
+    Ok(seeds)
+}
+
+fn parse_mapping(lines: &[Line]) -> Result<BTreeMap<i64, i64>, ParseError> {
+    let mut mapping: BTreeMap<i64, i64> = [(0, 0)].into_iter().collect();
+    for line in lines {
+        let vals: Vec<i64> = line.numbers(line.text, "a number")?;
+        if vals.len() != 3 {
+            return Err(line.error(line.text, "`<destination> <source> <length>`"));
+        }
+        // both ends of the range must fit, so that translating within it cannot overflow
+        let Some(end) = vals[1]
+            .checked_add(vals[2])
+            .filter(|_| vals[0].checked_add(vals[2]).is_some())
+        else {
+            let length = line.text.split_whitespace().nth(2).unwrap();
+            return Err(line.error(length, "a length that keeps the range in 64 bits"));
+        };
+
+        mapping.insert(vals[1], vals[0]);
+        mapping.entry(end).or_insert(end);
+    }
+    Ok(mapping)
+}
+
+fn parse_mappings(sections: &mut Sections) -> Result<Mappings, ParseError> {
+    let mut next = || {
+        let section = sections.expect("a `map:` header")?;
+        let (_, body) = section.named(" map:", "a `<from>-to-<to> map:` header")?;
+        parse_mapping(body)
+    };
+    Ok(Mappings {
+        seed2soil: next()?,
+        soil2fert: next()?,
+        fert2water: next()?,
+        water2light: next()?,
+        light2temp: next()?,
+        temp2humid: next()?,
+        humid2loc: next()?,
+    })
+}
+
+/// Follows a seed through all the maps.
+pub fn resolve_seed(seed: &i64, mappings: &Mappings) -> Seed {
+    let mut s: Seed = Default::default();
+    s.seed = *seed;
+    s.soil = translate(&mappings.seed2soil, &s.seed);
+    s.fert = translate(&mappings.soil2fert, &s.soil);
+    s.water = translate(&mappings.fert2water, &s.fert);
+    s.light = translate(&mappings.water2light, &s.water);
+    s.temp = translate(&mappings.light2temp, &s.light);
+    s.humid = translate(&mappings.temp2humid, &s.temp);
+    s.loc = translate(&mappings.humid2loc, &s.humid);
+    debug!(
+        "seed {} -> soil {} -> fertilizer {} -> water {} -> light {} -> temperature {} \
+         -> humidity {} -> location {}",
+        s.seed, s.soil, s.fert, s.water, s.light, s.temp, s.humid, s.loc
+    );
+    s
+}
+
+// ----------------------------------------------------------------------------
+/// Maps the numbers in `[begin, end)`, which must not be empty, through a
+/// map. The range is split at every breakpoint, so the result is one range
+/// per piece.
+pub fn translate_range(mapping: &BTreeMap<i64, i64>, (begin, end): (i64, i64)) -> Vec<(i64, i64)> {
+    let mut pieces: Vec<(i64, i64)> = Vec::new();
+    let mut from = begin;
+    for (breakpoint, _) in mapping.range((Excluded(begin), Excluded(end))) {
+        pieces.push((from, *breakpoint));
+        from = *breakpoint;
+    }
+    pieces.push((from, end));
+
+    pieces
+        .into_iter()
+        .map(|(b, e)| {
+            let tb = translate(mapping, &b);
+            (tb, tb + e - b)
+        })
+        .collect()
+}
+
+// ----------------------------------------------------------------------------
+const CATEGORIES: [&str; 8] = [
+    "seed",
+    "soil",
+    "fertilizer",
+    "water",
+    "light",
+    "temperature",
+    "humidity",
+    "location",
+];
+
+// every map has `ranges` lines whose sources never overlap
+fn random_almanac(rng: &mut Rng, ranges: usize) -> String {
+    const SPACE: u64 = 1 << 32;
+    let seeds: Vec<String> = (0..10)
+        .map(|_| {
+            let start = rng.below(SPACE / 2);
+            format!("{} {}", start, rng.range(1..SPACE / 16))
+        })
+        .collect();
+    let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+
+    for pair in CATEGORIES.windows(2) {
+        almanac += &format!("\n{}-to-{} map:\n", pair[0], pair[1]);
+        let mut breakpoints: Vec<u64> = (0..ranges.max(1) * 2).map(|_| rng.below(SPACE)).collect();
+        breakpoints.sort();
+        breakpoints.dedup();
+        let mut lines: Vec<String> = breakpoints
+            .chunks_exact(2)
+            .map(|source| {
+                let length = source[1] - source[0];
+                format!("{} {} {}", rng.below(SPACE - length), source[0], length)
+            })
+            .collect();
+        rng.shuffle(&mut lines);
+        for line in lines {
+            almanac += &line;
+            almanac.push('\n');
+        }
+    }
+    almanac
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the text into an [`Almanac`].
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Almanac;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut sections = parse::sections(Self::DAY, input);
+        let seeds = parse_seeds(sections.expect("`seeds:`")?.single()?)?;
+        let mappings = parse_mappings(&mut sections)?;
+        sections.end()?;
+        Ok(Almanac { seeds, mappings })
+    }
+
+    fn part1(almanac: &Self::Input) -> i64 {
+        almanac
+            .seeds
+            .iter()
+            .map(|s| resolve_seed(s, &almanac.mappings).loc)
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn part2(almanac: &Self::Input) -> i64 {
+        let mut ranges: Vec<(i64, i64)> = almanac
+            .seeds
+            .chunks(2)
+            .map(|pair| (pair[0], pair[0] + pair[1]))
+            // a range of length 0 holds no seeds, so its start must not count
+            .filter(|(begin, end)| begin < end)
+            .collect();
+        for mapping in [
+            &almanac.mappings.seed2soil,
+            &almanac.mappings.soil2fert,
+            &almanac.mappings.fert2water,
+            &almanac.mappings.water2light,
+            &almanac.mappings.light2temp,
+            &almanac.mappings.temp2humid,
+            &almanac.mappings.humid2loc,
+        ] {
+            ranges = ranges
+                .into_iter()
+                .flat_map(|range| translate_range(mapping, range))
+                .collect();
+        }
+
+        ranges.iter().map(|(begin, _)| *begin).min().unwrap_or(0)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_almanac(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    aoc_common::fixture_tests!(Day05);
+
+    // the seed ranges and the `(destination, source, length)` lines of each map
+    type RawAlmanac = (Vec<(i64, i64)>, Vec<Vec<(i64, i64, i64)>>);
+
+    fn render((seeds, maps): &RawAlmanac) -> String {
+        let seeds: Vec<String> = seeds
+            .iter()
+            .map(|(start, length)| format!("{} {}", start, length))
+            .collect();
+        let mut almanac = format!("seeds: {}\n", seeds.join(" "));
+        for (pair, lines) in CATEGORIES.windows(2).zip(maps) {
+            almanac += &format!("\n{}-to-{} map:\n", pair[0], pair[1]);
+            for (destination, source, length) in lines {
+                almanac += &format!("{} {} {}\n", destination, source, length);
+            }
+        }
+        almanac
+    }
+
+    fn random_raw_almanac(rng: &mut Rng) -> RawAlmanac {
+        let seeds = (0..rng.index(1..4))
+            .map(|_| (rng.below(64) as i64, rng.range(1..16) as i64))
+            .collect();
+        let maps = (0..7)
+            .map(|_| {
+                let mut breakpoints: Vec<i64> = (0..rng.index(0..4) * 2)
+                    .map(|_| rng.below(80) as i64)
+                    .collect();
+                breakpoints.sort();
+                breakpoints.dedup();
+                breakpoints
+                    .chunks_exact(2)
+                    .map(|source| (rng.below(80) as i64, source[0], source[1] - source[0]))
+                    .collect()
+            })
+            .collect();
+        (seeds, maps)
+    }
+
+    // lengths stay positive so that the source ranges never start to overlap
+    fn shrink_raw_almanac((seeds, maps): &RawAlmanac) -> Vec<RawAlmanac> {
+        let smaller = |n: i64| {
+            property::shrink_number(n as u64)
+                .into_iter()
+                .map(|n| n as i64)
+        };
+        let shorter = |length: i64| smaller(length - 1).map(|length| length + 1);
+
+        let mut candidates: Vec<RawAlmanac> = property::shrink_vec(seeds, |&(start, length)| {
+            smaller(start)
+                .map(|start| (start, length))
+                .chain(shorter(length).map(|length| (start, length)))
+                .collect()
+        })
+        .into_iter()
+        .filter(|seeds| !seeds.is_empty())
+        .map(|seeds| (seeds, maps.clone()))
+        .collect();
+        for (i, lines) in maps.iter().enumerate() {
+            let shrunk = property::shrink_vec(lines, |&(destination, source, length)| {
+                smaller(destination)
+                    .map(|destination| (destination, source, length))
+                    .chain(shorter(length).map(|length| (destination, source, length)))
+                    .collect()
+            });
+            for lines in shrunk {
+                let mut maps = maps.clone();
+                maps[i] = lines;
+                candidates.push((seeds.clone(), maps));
+            }
+        }
+        candidates
+    }
+
+    // the lowest location of any seed, translating one number at a time
+    fn brute_force_lowest((seeds, maps): &RawAlmanac, seed_ranges: bool) -> i64 {
+        let locate = |seed: i64| {
+            maps.iter().fold(seed, |n, lines| {
+                lines
+                    .iter()
+                    .find(|&&(_, source, length)| (source..source + length).contains(&n))
+                    .map_or(n, |&(destination, source, _)| destination + n - source)
+            })
+        };
+        let seeds: Vec<i64> = if seed_ranges {
+            seeds
+                .iter()
+                .flat_map(|&(start, length)| start..start + length)
+                .collect()
+        } else {
+            seeds
+                .iter()
+                .flat_map(|&(start, length)| [start, length])
+                .collect()
+        };
+        seeds.into_iter().map(locate).min().unwrap()
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        property::equivalent(
+            property::CASES,
+            random_raw_almanac,
+            shrink_raw_almanac,
+            |raw| {
+                let almanac = Day05::parse(&render(raw)).unwrap();
+                (Day05::part1(&almanac), Day05::part2(&almanac))
+            },
+            |raw| {
+                (
+                    brute_force_lowest(raw, false),
+                    brute_force_lowest(raw, true),
+                )
+            },
+        );
+    }
+
+    #[test]
+    fn test_empty_seed_range() {
+        let input = Day05::EXAMPLE.replacen("seeds: 79 14 55 13", "seeds: 0 0 55 13", 1);
+        let almanac = Day05::parse(&input).unwrap();
+        // seed 0 would end up at location 22, below the 56 of the other range
+        assert_eq!(Day05::part2(&almanac), 56);
+    }
+
+    #[test]
+    fn test_translation() {
+        let input = r"50 98 2
+52 50 48
+
+";
+        let mapping = parse_mapping(&parse::records(5, input)).unwrap();
+        assert_eq!(translate(&mapping, &0), 0);
+        assert_eq!(translate(&mapping, &1), 1);
+        assert_eq!(translate(&mapping, &49), 49);
+        assert_eq!(translate(&mapping, &50), 52);
+        assert_eq!(translate(&mapping, &51), 53);
+        assert_eq!(translate(&mapping, &97), 99);
+        assert_eq!(translate(&mapping, &98), 50);
+        assert_eq!(translate(&mapping, &99), 51);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day05::parse("seeds: 79 14 55\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(5, 1, 16, "a seed range length", "end of line")
+        );
+
+        let err = Day05::parse("seeds: 79 14\n\nseed-to-soil map:\n50 98\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(5, 4, 1, "`<destination> <source> <length>`", "`50 98`")
+        );
+
+        let err = Day05::parse("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(5, 5, 1, "a `map:` header", "end of input")
+        );
+
+        let err = Day05::parse("seeds 79 14\n").unwrap_err();
+        assert_eq!(err, ParseError::new(5, 1, 1, "`seeds:`", "`seeds 79 14`"));
+
+        let err = Day05::parse("seeds: 79 14\n\nseed-to-soil:\n50 98 2\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(5, 3, 1, "a `<from>-to-<to> map:` header", "`seed-to-soil:`")
+        );
+
+        let input = "seeds: 79 14\n\nseed-to-soil map:\n50 9223372036854775800 8\n";
+        let err = Day05::parse(input).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(5, 4, 24, "a length that keeps the range in 64 bits", "`8`")
+        );
+    }
+}
@@ -0,0 +1,216 @@
+//! Day 6: Wait For It
+//!
+//! Holding a boat's button for `x` milliseconds of a `time` long race makes
+//! it travel `x * (time - x)`. Part 1 counts the ways to beat each record,
+//! part 2 reads all the races as one long race.
+
+use aoc_common::math;
+use aoc_common::parse::{self, Line};
+use aoc_common::{trace, ParseError, Rng, Solution};
+
+/// The races on the sheet, and the one race they make when the spaces
+/// between their digits are ignored.
+#[derive(Debug, PartialEq)]
+pub struct Sheet {
+    pub races: Vec<(u64, u64)>,
+    pub merged: (u64, u64),
+}
+
+// the digits of all the tokens written one after the other, as one number,
+// so that leading zeros are kept
+fn concat<S: AsRef<str>>(tokens: &[S]) -> Option<u64> {
+    tokens
+        .iter()
+        .flat_map(|token| token.as_ref().chars())
+        .try_fold(0u64, |acc, c| {
+            acc.checked_mul(10)?.checked_add(c.to_digit(10)? as u64)
+        })
+}
+
+// the numbers on a row, and the number their digits make together
+fn parse_row(line: &Line, label: &str) -> Result<(Vec<u64>, u64), ParseError> {
+    let numbers: Vec<u64> = line.key_values(label, "a number")?;
+    let values = line.value_of(label)?;
+    let tokens: Vec<&str> = values.split_whitespace().collect();
+    match concat(&tokens) {
+        Some(merged) => Ok((numbers, merged)),
+        None => Err(line.error(values.trim(), "numbers that together fit in 64 bits")),
+    }
+}
+
+fn parse_races(input: &str, lines: &[Line]) -> Result<Sheet, ParseError> {
+    let expected = "a `Time:` and a `Distance:` line";
+    let [times, distances] = lines else {
+        return Err(match lines.get(2) {
+            Some(line) => ParseError::new(Day06::DAY, line.number, 1, expected, "another line"),
+            None => parse::end_of_input(Day06::DAY, input, expected),
+        });
+    };
+    let (times, time) = parse_row(times, "Time")?;
+    let (distances, distance) = parse_row(distances, "Distance")?;
+    if times.len() != distances.len() {
+        return Err(lines[1].error_at_end(&format!("{} distances", times.len())));
+    }
+    Ok(Sheet {
+        races: times.into_iter().zip(distances).collect(),
+        merged: (time, distance),
+    })
+}
+
+/// The number of ways to hold the button for `x` with
+/// `x * (time - x) > dist`.
+pub fn n_ways(time: u64, dist: u64) -> u64 {
+    let (time, dist) = (time as u128, dist as u128);
+    let Some(discr) = (time * time).checked_sub(4 * dist) else {
+        return 0;
+    };
+    let beats = |x: u128| x * (time - x) > dist;
+
+    // start at or just below the lower root (time - sqrt(discr)) / 2
+    let mut x = (time - math::isqrt_u128(discr)) / 2;
+    while x <= time / 2 && !beats(x) {
+        x += 1;
+    }
+    if x > time / 2 {
+        return 0;
+    }
+    trace!(
+        "race of {} ms, record {}: wins from {} to {} ms",
+        time,
+        dist,
+        x,
+        time - x
+    );
+    // the winning hold times are symmetric around time / 2
+    (time - 2 * x + 1) as u64
+}
+
+// ----------------------------------------------------------------------------
+// up to four races, each of which (and their merged race) can be won
+fn random_races(rng: &mut Rng, races: usize) -> String {
+    loop {
+        let times: Vec<u64> = (0..races.clamp(1, 4)).map(|_| rng.range(7..100)).collect();
+        let dists: Vec<u64> = times
+            .iter()
+            .map(|&t| rng.below((t / 2) * (t - t / 2)).min(999))
+            .collect();
+        let tokens = |values: &[u64]| values.iter().map(u64::to_string).collect::<Vec<_>>();
+        let merged = concat(&tokens(&times)).zip(concat(&tokens(&dists)));
+        if merged.is_some_and(|(t, d)| n_ways(t, d) > 0) {
+            let row = |values: &[u64]| {
+                let values: Vec<String> = values.iter().map(|v| format!("{:>4}", v)).collect();
+                values.concat()
+            };
+            return format!("Time:    {}\nDistance:{}\n", row(&times), row(&dists));
+        }
+    }
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the sheet into a [`Sheet`] of
+/// `(time, distance)` races.
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Sheet;
+    type Answer = Option<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_races(input, &parse::records(Self::DAY, input))
+    }
+
+    fn part1(sheet: &Self::Input) -> Option<u64> {
+        if sheet.races.is_empty() {
+            return Some(0);
+        }
+        sheet
+            .races
+            .iter()
+            .try_fold(1u64, |acc, &(t, d)| acc.checked_mul(n_ways(t, d)))
+    }
+
+    fn part2(sheet: &Self::Input) -> Option<u64> {
+        let (t, d) = sheet.merged;
+        Some(n_ways(t, d))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_races(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day06);
+
+    #[test]
+    fn test_n_ways() {
+        assert_eq!(n_ways(7, 9), 4);
+        assert_eq!(n_ways(15, 40), 8);
+        assert_eq!(n_ways(30, 200), 9);
+        // touching the record is not enough, and some races cannot be won
+        assert_eq!(n_ways(4, 4), 0);
+        assert_eq!(n_ways(4, 3), 1);
+        assert_eq!(n_ways(3, 100), 0);
+        assert_eq!(n_ways(u64::MAX, 0), u64::MAX - 1);
+    }
+
+    #[test]
+    fn test_large_race() {
+        let sheet = Day06::parse("Time: 9999999999999999999\nDistance: 0\n").unwrap();
+        assert_eq!(Day06::part1(&sheet), Some(9999999999999999998));
+        assert_eq!(Day06::part2(&sheet), Some(9999999999999999998));
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        let sheet = Day06::parse("Time: 7 015\nDistance: 9 040\n").unwrap();
+        assert_eq!(sheet.races, [(7, 9), (15, 40)]);
+        assert_eq!(sheet.merged, (7015, 9040));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day06::parse("Time: 7 15\nDistance: 9\n").unwrap_err();
+        assert_eq!(err, ParseError::new(6, 2, 12, "2 distances", "end of line"));
+
+        let err = Day06::parse("Time: 7 1x\nDistance: 9 40\n").unwrap_err();
+        assert_eq!(err, ParseError::new(6, 1, 9, "a number", "`1x`"));
+
+        let err = Day06::parse("Time: 7 15\nDistance: 9 99999999999999999999\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(6, 2, 13, "a number", "`99999999999999999999`")
+        );
+
+        let err = Day06::parse("Time: 7 9999999999999999999\nDistance: 9 40\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                6,
+                1,
+                7,
+                "numbers that together fit in 64 bits",
+                "`7 9999999999999999999`"
+            )
+        );
+
+        let err = Day06::parse("Time: 7 15\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(6, 2, 1, "a `Time:` and a `Distance:` line", "end of input")
+        );
+
+        let err = Day06::parse("\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(6, 2, 1, "a `Time:` and a `Distance:` line", "end of input")
+        );
+    }
+}
@@ -0,0 +1,313 @@
+//! Day 7: Camel Cards
+//!
+//! Hands of five cards are ranked by type, then card by card. In part 2,
+//! `J` is a joker that acts as whatever card makes the best type, but ranks
+//! lowest on its own.
+
+use lazy_static::lazy_static;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::parse::{self, Line};
+use aoc_common::{ParseError, Rng, Solution};
+
+struct Hand {
+    power: usize,
+    cards: String,
+    bid: usize,
+}
+
+lazy_static! {
+    static ref MAPPING: Vec<(char, i64)> = vec![
+        ('A', 14),
+        ('K', 13),
+        ('Q', 12),
+        ('J', 11),
+        ('T', 10),
+        ('9', 9),
+        ('8', 8),
+        ('7', 7),
+        ('6', 6),
+        ('5', 5),
+        ('4', 4),
+        ('3', 3),
+        ('2', 2),
+    ];
+    static ref CARD_VALS: HashMap<char, i64> = MAPPING.iter().map(|&(c, v)| (c, v)).collect();
+}
+
+lazy_static! {
+    static ref MAPPING2: Vec<(char, i64)> = vec![
+        ('A', 14),
+        ('K', 13),
+        ('Q', 12),
+        ('T', 10),
+        ('9', 9),
+        ('8', 8),
+        ('7', 7),
+        ('6', 6),
+        ('5', 5),
+        ('4', 4),
+        ('3', 3),
+        ('2', 2),
+        ('J', 1),
+    ];
+    static ref CARD_VALS2: HashMap<char, i64> = MAPPING2.iter().map(|&(c, v)| (c, v)).collect();
+}
+
+fn compare_with_mapping(a: &Hand, b: &Hand, mapping: &HashMap<char, i64>) -> Ordering {
+    if a.power == b.power {
+        for (l, r) in a.cards.chars().zip(b.cards.chars()) {
+            if mapping.get(&l).unwrap() < mapping.get(&r).unwrap() {
+                return Ordering::Less;
+            } else if mapping.get(&l).unwrap() > mapping.get(&r).unwrap() {
+                return Ordering::Greater;
+            }
+        }
+        Ordering::Equal
+    } else {
+        a.power.cmp(&b.power)
+    }
+}
+
+fn compare(a: &Hand, b: &Hand) -> Ordering {
+    compare_with_mapping(a, b, &CARD_VALS)
+}
+
+fn compare2(a: &Hand, b: &Hand) -> Ordering {
+    compare_with_mapping(a, b, &CARD_VALS2)
+}
+
+/// The type of a hand, from 0 for a high card up to 6 for five of a kind,
+/// with or without `J` as a joker.
+pub fn calc_power(cards: &str, has_jokers: bool) -> usize {
+    let mut map: HashMap<char, usize> = Default::default();
+    for c in cards.chars() {
+        map.insert(c, map.get(&c).unwrap_or(&0) + 1);
+    }
+
+    if has_jokers {
+        let jokers = *map.get(&'J').unwrap_or(&0);
+        if map.len() > 1 {
+            map.remove(&'J');
+            let (k, v) = map.iter().max_by(|(_, v1), (_, v2)| v1.cmp(v2)).unwrap();
+            map.insert(*k, v + jokers);
+        }
+    }
+
+    match map.len() {
+        1 => 6,
+        2 => {
+            if *map.values().max().unwrap() == 4 {
+                5
+            } else {
+                4
+            }
+        }
+        3 => {
+            if *map.values().max().unwrap() == 3 {
+                3
+            } else {
+                2
+            }
+        }
+        4 => 1,
+        _ => 0,
+    }
+}
+
+fn parse_hand(line: &Line) -> Result<(String, usize), ParseError> {
+    let mut tokens = line.text.split_whitespace();
+    let hand = tokens.next().unwrap_or_default();
+    if hand.chars().count() != 5 || !hand.chars().all(|c| CARD_VALS.contains_key(&c)) {
+        return Err(line.error(hand, "five cards out of `AKQJT98765432`"));
+    }
+    let bid = match tokens.next() {
+        Some(bid) => line.parse(bid, "a bid")?,
+        None => return Err(line.error_at_end("a bid")),
+    };
+    if let Some(extra) = tokens.next() {
+        return Err(line.error(extra, "end of line"));
+    }
+    Ok((hand.to_string(), bid))
+}
+
+/// The sum of every `(hand, bid)` pair's bid times the hand's rank, with or
+/// without `J` as a joker.
+pub fn total_winnings(bids: &[(String, usize)], has_jokers: bool) -> usize {
+    let mut hands: Vec<Hand> = bids
+        .iter()
+        .map(|(cards, bid)| Hand {
+            power: calc_power(cards, has_jokers),
+            cards: cards.clone(),
+            bid: *bid,
+        })
+        .collect();
+    hands.sort_by(if has_jokers { compare2 } else { compare });
+    let (_, res) = hands
+        .iter()
+        .enumerate()
+        .map(|(i, h)| (i, h.bid))
+        .reduce(|(_, bids), (i, bid)| (i, bids + (i + 1) * bid))
+        .unwrap();
+    res
+}
+
+// ----------------------------------------------------------------------------
+fn random_hands(rng: &mut Rng, hands: usize) -> String {
+    let cards: Vec<char> = MAPPING.iter().map(|&(c, _)| c).collect();
+    // there are only so many distinct hands
+    let hands = hands.clamp(1, 100_000);
+    let mut seen: HashSet<String> = HashSet::new();
+    let mut list = String::new();
+    while seen.len() < hands {
+        let hand: String = (0..5).map(|_| *rng.choose(&cards)).collect();
+        if seen.insert(hand.clone()) {
+            list += &format!("{} {}\n", hand, rng.range(1..1001));
+        }
+    }
+    list
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the list into `(hand, bid)`
+/// pairs.
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Vec<(String, usize)>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let bids: Vec<_> = parse::records(Self::DAY, input)
+            .iter()
+            .map(parse_hand)
+            .collect::<Result<_, _>>()?;
+        if bids.is_empty() {
+            return Err(parse::end_of_input(Self::DAY, input, "a hand"));
+        }
+        Ok(bids)
+    }
+
+    fn part1(bids: &Self::Input) -> usize {
+        total_winnings(bids, false)
+    }
+
+    fn part2(bids: &Self::Input) -> usize {
+        total_winnings(bids, true)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_hands(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    aoc_common::fixture_tests!(Day07);
+
+    // the type of a hand from how often each card appears, jokers included
+    fn brute_force_type(cards: &[char]) -> usize {
+        let mut counts: Vec<usize> = cards
+            .iter()
+            .map(|c| cards.iter().filter(|&d| d == c).count())
+            .collect();
+        counts.sort_by(|a, b| b.cmp(a));
+        match counts.as_slice() {
+            [5, ..] => 6,
+            [4, ..] => 5,
+            [3, 3, 3, 2, 2] => 4,
+            [3, ..] => 3,
+            [2, 2, 2, 2, 1] => 2,
+            [2, ..] => 1,
+            _ => 0,
+        }
+    }
+
+    // the best type over every card that each joker could stand for
+    fn brute_force_joker_type(cards: &[char]) -> usize {
+        match cards.iter().position(|&c| c == 'J') {
+            None => brute_force_type(cards),
+            Some(i) => MAPPING2
+                .iter()
+                .filter(|&&(c, _)| c != 'J')
+                .map(|&(c, _)| {
+                    let mut cards = cards.to_vec();
+                    cards[i] = c;
+                    brute_force_joker_type(&cards)
+                })
+                .max()
+                .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_calc_power_matches_brute_force() {
+        property::equivalent(
+            property::CASES,
+            // few distinct cards and plenty of jokers, to hit every type
+            |rng| {
+                (0..5)
+                    .map(|_| *rng.choose(&['J', 'J', 'A', 'K', 'Q', 'T', '2']))
+                    .collect::<String>()
+            },
+            // hands shrink towards `AAAAA`
+            |hand| {
+                (0..5)
+                    .filter(|&i| &hand[i..i + 1] != "A")
+                    .map(|i| format!("{}A{}", &hand[..i], &hand[i + 1..]))
+                    .collect()
+            },
+            |hand| (calc_power(hand, false), calc_power(hand, true)),
+            |hand| {
+                let cards: Vec<char> = hand.chars().collect();
+                (brute_force_type(&cards), brute_force_joker_type(&cards))
+            },
+        );
+    }
+
+    #[test]
+    fn test_calc_power() {
+        assert_eq!(calc_power("AAAAA", false), 6);
+        assert_eq!(calc_power("AAJAA", false), 5);
+        assert_eq!(calc_power("AJJAA", false), 4);
+        assert_eq!(calc_power("AJ9AA", false), 3);
+        assert_eq!(calc_power("AJ99A", false), 2);
+        assert_eq!(calc_power("AJ98A", false), 1);
+        assert_eq!(calc_power("AJ98T", false), 0);
+    }
+
+    #[test]
+    fn test_calc_power2() {
+        assert_eq!(calc_power("AAAAA", true), 6);
+        assert_eq!(calc_power("AAJAA", true), 6);
+        assert_eq!(calc_power("AJJAA", true), 6);
+        assert_eq!(calc_power("KTJJT", true), 5);
+        assert_eq!(calc_power("AJ9AA", true), 5);
+        assert_eq!(calc_power("AJ99A", true), 4);
+        assert_eq!(calc_power("AJ98A", true), 3);
+        assert_eq!(calc_power("AJ98T", true), 1);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day07::parse("32T3K 765\nT55X5 684\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(7, 2, 1, "five cards out of `AKQJT98765432`", "`T55X5`")
+        );
+
+        let err = Day07::parse("32T3K x\n").unwrap_err();
+        assert_eq!(err, ParseError::new(7, 1, 7, "a bid", "`x`"));
+
+        let err = Day07::parse("\n").unwrap_err();
+        assert_eq!(err, ParseError::new(7, 2, 1, "a hand", "end of input"));
+    }
+}
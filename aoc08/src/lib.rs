@@ -0,0 +1,433 @@
+//! Day 8: Haunted Wasteland
+//!
+//! A network of nodes, each with a left and a right neighbour, is walked by
+//! following a repeating list of directions. Part 1 walks from `AAA` to
+//! `ZZZ`, part 2 walks from every `..A` node at once until all the walkers
+//! stand on `..Z` nodes.
+
+use std::collections::{HashMap, HashSet};
+
+use aoc_common::parse::{self, Line};
+use aoc_common::{cycle, math};
+use aoc_common::{debug, span, CancellationToken, Cancelled, ParseError, Rng, Solution};
+use regex::Regex;
+
+/// The directions, made of `L` and `R`, and the network, which maps every
+/// node to its neighbour in each direction.
+#[derive(Debug)]
+pub struct Map {
+    pub directions: String,
+    pub network: HashMap<String, HashMap<char, String>>,
+}
+
+fn parse_directions(line: &Line) -> Result<String, ParseError> {
+    line.check_chars("LR", "`L` or `R`")?;
+    Ok(line.text.to_string())
+}
+
+fn parse_network(lines: &[Line]) -> Result<HashMap<String, HashMap<char, String>>, ParseError> {
+    let mut network: HashMap<String, HashMap<char, String>> = Default::default();
+    let re = Regex::new(r"^(\w+) = \((\w+), (\w+)\)$").unwrap();
+    let mut targets: Vec<(&Line, &str)> = Vec::new();
+    for line in lines {
+        let Some(caps) = re.captures(line.text) else {
+            return Err(line.error(line.text, "`<node> = (<left>, <right>)`"));
+        };
+        let (from, left, right) = (&caps[1], caps.get(2).unwrap(), caps.get(3).unwrap());
+        targets.push((line, &line.text[left.range()]));
+        targets.push((line, &line.text[right.range()]));
+        let directions: HashMap<char, String> = vec![
+            ('L', left.as_str().to_string()),
+            ('R', right.as_str().to_string()),
+        ]
+        .into_iter()
+        .collect();
+        network.insert(from.to_string(), directions);
+    }
+    for (line, target) in targets {
+        if !network.contains_key(target) {
+            return Err(line.error(target, "a node defined in the network"));
+        }
+    }
+    Ok(network)
+}
+
+// ----------------------------------------------------------------------------
+// the cycle a ghost ends up in, and the steps before the end of its first
+// lap around it at which the ghost stands on a Z node
+fn calc_individual_paths(
+    start: &String,
+    directions: &str,
+    network: &HashMap<String, HashMap<char, String>>,
+) -> (cycle::Cycle, Vec<usize>) {
+    let next_step = |it| directions.chars().nth(it % directions.len()).unwrap();
+
+    // the state is the current node and how far into the directions we are
+    let (cycle, history) = cycle::hashed((start, 0), |&(current, i)| {
+        (&network[current][&next_step(i)], (i + 1) % directions.len())
+    });
+
+    // Z nodes in the prefix count too: they are where the ghost stands on
+    // steps before all the ghosts have reached their cycles
+    let possible_endpoints = history
+        .states()
+        .iter()
+        .enumerate()
+        .filter(|(_, (current, _))| current.ends_with('Z'))
+        .map(|(num_iterations, _)| num_iterations)
+        .collect();
+    debug!(
+        "ghost from {}: cycle of {} steps after {}, on Z nodes at steps {:?}",
+        start, cycle.length, cycle.prefix, possible_endpoints
+    );
+    (cycle, possible_endpoints)
+}
+
+/// The number of steps from `AAA` to `ZZZ`, or `None` if the network lacks
+/// either node or the walk comes back to a node at the same point in the
+/// directions without having reached `ZZZ`, after which it would go round
+/// forever.
+pub fn walk(map: &Map, token: &CancellationToken) -> Result<Option<usize>, Cancelled> {
+    if !map.network.contains_key("ZZZ") {
+        return Ok(None);
+    }
+    let mut num_iterations: usize = 0;
+    let mut current = "AAA";
+    // the state is the current node and how far into the directions we are
+    let mut history = cycle::History::new();
+    while current != "ZZZ" {
+        token.check()?;
+        let i = num_iterations % map.directions.len();
+        if history.push((current, i)).is_some() {
+            debug!("walk from AAA loops after {} steps", num_iterations);
+            return Ok(None);
+        }
+        let step = map.directions.chars().nth(i).unwrap();
+        let Some(next) = map.network.get(current) else {
+            return Ok(None);
+        };
+        current = &next[&step];
+        num_iterations += 1;
+    }
+
+    Ok(Some(num_iterations))
+}
+
+/// The number of steps until the walkers that start on every `..A` node
+/// all stand on `..Z` nodes, or `None` if they never do.
+///
+/// Until every walker has reached its cycle, each step is checked directly.
+/// From then on every walker is on a Z node at step n exactly when
+/// n ≡ endpoint (mod cycle length) for one of the endpoints in its cycle.
+pub fn ghost_walk(map: &Map, token: &CancellationToken) -> Result<Option<usize>, Cancelled> {
+    let current: Vec<&String> = map.network.keys().filter(|k| k.ends_with('A')).collect();
+    let _span = span!("ghost walk from {} nodes", current.len());
+    let playouts: Vec<(cycle::Cycle, Vec<usize>)> = current
+        .iter()
+        .map(|c| calc_individual_paths(c, &map.directions, &map.network))
+        .collect();
+
+    let start = playouts.iter().map(|(c, _)| c.prefix).max().unwrap_or(0);
+    for n in 0..start {
+        token.check()?;
+        let on_z = |(cycle, endpoints): &(cycle::Cycle, Vec<usize>)| {
+            endpoints.contains(&cycle.reduce(n as u128))
+        };
+        if playouts.iter().all(on_z) {
+            debug!("all ghosts on Z nodes at step {}, before the cycles", n);
+            return Ok(Some(n));
+        }
+    }
+
+    // solve the congruences one ghost at a time, keeping every combination of endpoints
+    let mut solutions: Vec<(u64, u64)> = vec![(0, 1)];
+    for (cycle, endpoints) in &playouts {
+        let mut next = Vec::new();
+        for &solution in &solutions {
+            token.check()?;
+            next.extend(
+                endpoints
+                    .iter()
+                    .filter(|&&e| e >= cycle.prefix)
+                    .filter_map(|&e| math::crt(&[solution, (e as u64, cycle.length as u64)])),
+            );
+        }
+        solutions = next;
+        debug!("{} combinations of endpoints left", solutions.len());
+    }
+
+    let start = start as u64;
+    let first_at_or_after = |(x, m): (u64, u64)| {
+        if x >= start {
+            x
+        } else {
+            x + (start - x).div_ceil(m) * m
+        }
+    };
+    // no solution left means some ghosts are never on Z nodes at the same step
+    let steps = solutions.into_iter().map(first_at_or_after).min();
+    Ok(steps.map(|steps| steps as usize))
+}
+
+// ----------------------------------------------------------------------------
+fn random_name(rng: &mut Rng, len: usize) -> String {
+    (0..len)
+        .map(|_| char::from(b'A' + rng.below(26) as u8))
+        .collect()
+}
+
+// Every ghost walks its own loop `..A -> ... -> ..Z -> (back to the first
+// node after ..A)`, whichever way the directions point, so it stands on its Z
+// node exactly every `length` steps and all of them meet at the least common
+// multiple. AAA is the first ghost, so ZZZ is always reachable.
+fn random_network(rng: &mut Rng, size: usize) -> String {
+    let directions: String = (0..rng.index(1..size / 4 + 2))
+        .map(|_| if rng.chance(1, 2) { 'L' } else { 'R' })
+        .collect();
+
+    let mut used: HashSet<String> = HashSet::new();
+    let ghosts = (size / 50).clamp(1, 6);
+    let mut nodes: Vec<String> = Vec::new();
+    for ghost in 0..ghosts {
+        let prefix = if ghost == 0 {
+            "AA".to_string()
+        } else {
+            loop {
+                let prefix = random_name(rng, 2);
+                if !used.contains(&format!("{}A", prefix))
+                    && !used.contains(&format!("{}Z", prefix))
+                {
+                    break prefix;
+                }
+            }
+        };
+        let end = if ghost == 0 {
+            "ZZZ".to_string()
+        } else {
+            format!("{}Z", prefix)
+        };
+        let mut walk = vec![format!("{}A", prefix)];
+        for _ in 1..rng.index(2..(size / ghosts).clamp(3, 500)) {
+            walk.push(loop {
+                // never ending in A or Z
+                let name = random_name(rng, 2) + &random_name(rng, 1).replace(['A', 'Z'], "M");
+                if !used.contains(&name) && !walk.contains(&name) {
+                    break name;
+                }
+            });
+        }
+        walk.push(end);
+        walk.push(walk[1].clone());
+        used.extend(walk.iter().cloned());
+        for step in walk.windows(2) {
+            nodes.push(format!("{} = ({}, {})", step[0], step[1], step[1]));
+        }
+    }
+    rng.shuffle(&mut nodes);
+    format!("{}\n\n{}\n", directions, nodes.join("\n"))
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the document into a [`Map`].
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Map;
+    type Answer = Option<usize>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut sections = parse::sections(Self::DAY, input);
+        let directions = sections.expect("a line of directions")?;
+        // the nodes may be split up by further blank lines
+        let nodes: Vec<Line> = sections.flat_map(|section| section.lines).collect();
+        Ok(Map {
+            directions: parse_directions(directions.single()?)?,
+            network: parse_network(&nodes)?,
+        })
+    }
+
+    fn part1(map: &Self::Input) -> Option<usize> {
+        walk(map, &CancellationToken::new()).expect("the token is never cancelled")
+    }
+
+    fn part2(map: &Self::Input) -> Option<usize> {
+        Self::part2_cancellable(map, &CancellationToken::new())
+            .expect("the token is never cancelled")
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_network(rng, size)
+    }
+
+    fn part1_cancellable(
+        map: &Self::Input,
+        token: &CancellationToken,
+    ) -> Result<Option<usize>, Cancelled> {
+        walk(map, token)
+    }
+
+    fn part2_cancellable(
+        map: &Self::Input,
+        token: &CancellationToken,
+    ) -> Result<Option<usize>, Cancelled> {
+        ghost_walk(map, token)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day08);
+
+    #[test]
+    fn test_calc_individual_paths() {
+        let input = r"LR
+
+11A = (11B, XXX)
+11B = (XXX, 11Z)
+11Z = (11B, XXX)
+22A = (22B, XXX)
+22B = (22C, 22C)
+22C = (22Z, 22Z)
+22Z = (22B, 22B)
+XXX = (XXX, XXX)
+";
+        let map = Day08::parse(input).unwrap();
+        let (cycle, endpoints) =
+            calc_individual_paths(&"11A".to_string(), &map.directions, &map.network);
+        assert_eq!((cycle.prefix, cycle.length), (1, 2));
+        assert_eq!(endpoints, [2]);
+        let (cycle, endpoints) =
+            calc_individual_paths(&"22A".to_string(), &map.directions, &map.network);
+        assert_eq!((cycle.prefix, cycle.length), (1, 6));
+        assert_eq!(endpoints, [3, 6]);
+    }
+
+    // 11A is on a Z node at step 1, before its cycle, and at every odd step
+    // from 3 on; AAA is on one at every step from 1 on
+    const PREFIX_Z: &str = r"L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (12Z, 12Z)
+12Z = (11B, 11B)
+AAA = (ZZZ, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    #[test]
+    fn test_prefix_endpoints() {
+        let map = Day08::parse(PREFIX_Z).unwrap();
+        let (cycle, endpoints) =
+            calc_individual_paths(&"11A".to_string(), &map.directions, &map.network);
+        assert_eq!((cycle.prefix, cycle.length), (2, 2));
+        assert_eq!(endpoints, [1, 3]);
+        assert_eq!(Day08::part2(&map), Some(1));
+    }
+
+    #[test]
+    fn test_ghosts_never_meet() {
+        // 11A is only on a Z node at step 1, the ghost from AAA at every step from 1 on
+        let input = r"L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11B, 11B)
+AAA = (ZZZ, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+        let map = Day08::parse(input).unwrap();
+        assert_eq!(Day08::part2(&map), Some(1));
+
+        // 11A is only on a Z node at step 1 and 22A only at step 2
+        let input = r"L
+
+11A = (11Z, 11Z)
+11Z = (11B, 11B)
+11B = (11B, 11B)
+22A = (22B, 22B)
+22B = (22Z, 22Z)
+22Z = (22C, 22C)
+22C = (22C, 22C)
+";
+        let map = Day08::parse(input).unwrap();
+        assert_eq!(Day08::part2(&map), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day08::parse("LXR\n\nAAA = (AAA, AAA)\n").unwrap_err();
+        assert_eq!(err, ParseError::new(8, 1, 2, "`L` or `R`", "`X`"));
+
+        let err = Day08::parse("LR\n\nAAA = (AAA, ZZZ)\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(8, 3, 13, "a node defined in the network", "`ZZZ`")
+        );
+
+        let err = Day08::parse("LR\nAAA = (AAA, AAA)\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(8, 2, 1, "a blank line", "`AAA = (AAA, AAA)`")
+        );
+
+        let err = Day08::parse("\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(8, 2, 1, "a line of directions", "end of input")
+        );
+
+        let err = Day08::parse("LR\n\nAAA -> (AAA, AAA)\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                8,
+                3,
+                1,
+                "`<node> = (<left>, <right>)`",
+                "`AAA -> (AAA, AAA)`"
+            )
+        );
+    }
+
+    #[test]
+    fn test_missing_start() {
+        // a network for the ghosts alone parses, but part 1 has no answer on it
+        let map = Day08::parse("L\n\n11A = (11Z, 11Z)\n11Z = (11A, 11A)\n").unwrap();
+        assert_eq!(Day08::part1(&map), None);
+        let map = Day08::parse("L\n\nAAA = (AAA, AAA)\n").unwrap();
+        assert_eq!(Day08::part1(&map), None);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        // AAA and BBB lead to each other, and never to ZZZ
+        let map =
+            Day08::parse("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        assert_eq!(Day08::part1(&map), None);
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let map = Day08::parse("L\n\nAAA = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n").unwrap();
+        let token = CancellationToken::new();
+        token.cancel();
+        assert_eq!(Day08::part1_cancellable(&map, &token), Err(Cancelled));
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..10 {
+            let map = Day08::parse(&random_network(&mut Rng::new(seed), 300)).unwrap();
+            let ghosts = map.network.keys().filter(|k| k.ends_with('A')).count();
+            assert!(ghosts > 1);
+            assert!(Day08::part1(&map) > Some(0));
+            assert!(Day08::part2(&map) >= Day08::part1(&map));
+        }
+    }
+}
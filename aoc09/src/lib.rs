@@ -0,0 +1,121 @@
+//! Day 9: Mirage Maintenance
+//!
+//! Every history of the report is extrapolated by taking differences
+//! between neighbouring values until they are all zero, forwards in part 1
+//! and backwards in part 2.
+
+use aoc_common::parse::{self, Line};
+use aoc_common::{ParseError, Rng, Solution};
+
+fn parse_line(line: &Line) -> Result<Vec<i64>, ParseError> {
+    line.numbers(line.text, "a number")
+}
+
+fn calculate_differences(nums: &[i64]) -> Vec<Vec<i64>> {
+    let mut iter_diffs: Vec<Vec<i64>> = Default::default();
+    iter_diffs.push(nums.to_vec());
+    loop {
+        let last = iter_diffs.last().unwrap();
+        if last.len() == 1 {
+            iter_diffs.push(vec![0]);
+            break;
+        }
+        if last.iter().all(|n| *n == 0) {
+            break;
+        }
+        let diffs: Vec<i64> = last[0..last.len() - 1]
+            .iter()
+            .zip(last[1..].iter())
+            .map(|(a, b)| b - a)
+            .collect();
+        iter_diffs.push(diffs);
+    }
+    iter_diffs
+}
+
+/// The value that follows a history, which must not be empty.
+pub fn get_next(nums: &[i64]) -> i64 {
+    let mut iter_diffs = calculate_differences(nums);
+    for i in (1..iter_diffs.len()).rev() {
+        let next = iter_diffs[i - 1].last().unwrap() + iter_diffs[i].last().unwrap();
+        iter_diffs[i - 1].push(next);
+    }
+    *iter_diffs[0].last().unwrap()
+}
+
+// ----------------------------------------------------------------------------
+/// The value that precedes a history, which must not be empty.
+pub fn get_prev(nums: &[i64]) -> i64 {
+    let mut iter_diffs = calculate_differences(nums);
+    for i in (1..iter_diffs.len()).rev() {
+        let prev = iter_diffs[i - 1].first().unwrap() - iter_diffs[i].first().unwrap();
+        iter_diffs[i - 1].insert(0, prev);
+    }
+    *iter_diffs[0].first().unwrap()
+}
+
+// ----------------------------------------------------------------------------
+// every history is a polynomial of low degree, so the differences always
+// reach zero before the history runs out
+fn random_report(rng: &mut Rng, histories: usize) -> String {
+    let mut report = String::new();
+    for _ in 0..histories {
+        let coefficients: Vec<i64> = (0..rng.index(1..6))
+            .map(|_| rng.range(0..11) as i64 - 5)
+            .collect();
+        let values: Vec<String> = (0..21)
+            .map(|x| {
+                let value = coefficients.iter().rev().fold(0, |acc, c| acc * x + c);
+                value.to_string()
+            })
+            .collect();
+        report += &values.join(" ");
+        report.push('\n');
+    }
+    report
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the report into histories.
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Vec<Vec<i64>>;
+    type Answer = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::records(Self::DAY, input)
+            .iter()
+            .map(parse_line)
+            .collect()
+    }
+
+    fn part1(histories: &Self::Input) -> i64 {
+        histories.iter().map(|nums| get_next(nums)).sum()
+    }
+
+    fn part2(histories: &Self::Input) -> i64 {
+        histories.iter().map(|nums| get_prev(nums)).sum()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_report(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day09);
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day09::parse("0 3 6\n1 3 - 10\n").unwrap_err();
+        assert_eq!(err, ParseError::new(9, 2, 5, "a number", "`-`"));
+    }
+}
@@ -0,0 +1,329 @@
+//! Day 10: Pipe Maze
+//!
+//! The field is a grid of pipes, one loop of which runs through the start
+//! tile `S`. Part 1 asks for the tile on the loop farthest from the start,
+//! part 2 for the number of tiles the loop encloses.
+
+use std::ops::ControlFlow;
+
+use aoc_common::render::{Class, Colour, Picture};
+use aoc_common::search::{self, Graph, Search};
+use aoc_common::{parse, Direction, Grid, ParseError, Point, Rng, Solution};
+
+// the tiles every tile's pipe leads to
+struct Pipes(Grid<Vec<Point>>);
+
+impl Graph for Pipes {
+    type State = Point;
+
+    fn neighbours(&self, pos: &Point) -> Vec<Point> {
+        self.0[*pos].clone()
+    }
+}
+
+/// The two directions a pipe tile connects, if it is a pipe at all.
+pub fn pipe_ends(tile: char) -> Option<[Direction; 2]> {
+    match tile {
+        '|' => Some([Direction::N, Direction::S]),
+        '-' => Some([Direction::W, Direction::E]),
+        'L' => Some([Direction::N, Direction::E]),
+        'J' => Some([Direction::N, Direction::W]),
+        '7' => Some([Direction::S, Direction::W]),
+        'F' => Some([Direction::S, Direction::E]),
+        _ => None,
+    }
+}
+
+fn parse_connections(tiles: &Grid<char>) -> Pipes {
+    let mut pipes: Grid<Vec<Point>> = Grid::new(tiles.width(), tiles.height(), vec![]);
+
+    for (pos, tile) in tiles.iter() {
+        let pos = Point::from(pos);
+        for dir in pipe_ends(*tile).into_iter().flatten() {
+            if let Some(next) = tiles.step(pos, dir) {
+                pipes[pos].push(next);
+                if tiles[next] == 'S' {
+                    pipes[next].push(pos);
+                }
+            }
+        }
+    }
+    Pipes(pipes)
+}
+
+fn find_start(tiles: &Grid<char>) -> Point {
+    tiles
+        .find(|tile| *tile == 'S')
+        .map(Point::from)
+        .expect("parse checks for a start tile")
+}
+
+// the distance of every tile on the loop from the start
+fn max_fill(pipes: &Pipes, start: Point) -> Search<Point> {
+    search::bfs(pipes, [start], |_, _| ControlFlow::Continue(()))
+}
+
+/// How far the tile on the loop farthest from the start tile `S` is along
+/// the loop. The grid must have exactly one start tile.
+pub fn farthest_distance(tiles: &Grid<char>) -> usize {
+    let pipes = parse_connections(tiles);
+    let distances = max_fill(&pipes, find_start(tiles));
+    distances
+        .distances()
+        .map(|(_, dist)| dist)
+        .max()
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Region {
+    Loop,
+    Inside,
+    Outside,
+}
+
+// which side of the loop through the start tile every tile is on
+fn regions(tiles: &Grid<char>) -> Grid<Region> {
+    let pipes = parse_connections(tiles);
+    let distances = max_fill(&pipes, find_start(tiles));
+
+    // scan every row, flipping inside/outside whenever we cross a loop pipe that goes north
+    let mut regions = Grid::new(tiles.width(), tiles.height(), Region::Outside);
+    for (i, row) in pipes.0.rows().enumerate() {
+        let mut inside = false;
+        for (j, connections) in row.iter().enumerate() {
+            if distances.distance(&Point::new(i, j)).is_some() {
+                regions[(i, j)] = Region::Loop;
+                if i > 0 && connections.contains(&Point::new(i - 1, j)) {
+                    inside = !inside;
+                }
+            } else if inside {
+                regions[(i, j)] = Region::Inside;
+            }
+        }
+    }
+    regions
+}
+
+/// The number of tiles inside the loop through the start tile, which must
+/// be the only one.
+pub fn enclosed_tiles(tiles: &Grid<char>) -> usize {
+    regions(tiles)
+        .iter()
+        .filter(|(_, region)| **region == Region::Inside)
+        .count()
+}
+
+const START: Class = Class::new("start", Colour::rgb(230, 80, 60));
+const LOOP: Class = Class::new("loop", Colour::rgb(250, 190, 90));
+const INSIDE: Class = Class::new("inside", Colour::rgb(130, 210, 130));
+const OUTSIDE: Class = Class::new("outside", Colour::rgb(235, 235, 235));
+
+/// A picture of the field with the loop, and the tiles it encloses,
+/// coloured in.
+pub fn render_loop(tiles: &Grid<char>) -> Picture {
+    let regions = regions(tiles);
+    Picture::classify(tiles.clone(), |pos, tile| match (tile, regions[pos]) {
+        ('S', _) => START,
+        (_, Region::Loop) => LOOP,
+        (_, Region::Inside) => INSIDE,
+        (_, Region::Outside) => OUTSIDE,
+    })
+}
+
+// ----------------------------------------------------------------------------
+// extends the outline in a straight line to `to`
+fn trace_to(outline: &mut Vec<Point>, to: Point) {
+    let mut at = *outline.last().unwrap();
+    while at != to {
+        if at.row != to.row {
+            at.row = if at.row < to.row {
+                at.row + 1
+            } else {
+                at.row - 1
+            };
+        } else {
+            at.col = if at.col < to.col {
+                at.col + 1
+            } else {
+                at.col - 1
+            };
+        }
+        outline.push(at);
+    }
+}
+
+// The loop is the outline of a polygon made of one vertical run of cells per
+// column, each overlapping the next so that the outline never touches
+// itself. Its corners become the tiles of the loop; every other tile is junk.
+fn random_loop(rng: &mut Rng, size: usize) -> (Vec<Point>, usize) {
+    let size = size.max(3);
+    let first = rng.index(0..size / 4 + 1);
+    let last = size - 1 - rng.index(0..size / 4 + 1);
+
+    // the top and bottom of the polygon's run of cells in every column
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    let top = rng.index(0..size - 1);
+    runs.push((top, rng.index(top + 1..size)));
+    for _ in first + 1..last {
+        let (top, bottom) = *runs.last().unwrap();
+        let next_top = rng.index(0..bottom);
+        let next_bottom = rng.index(top.max(next_top) + 1..size);
+        runs.push((next_top, next_bottom));
+    }
+
+    let mut outline = vec![Point::new(runs[0].0, first)];
+    for (i, &(top, _)) in runs.iter().enumerate() {
+        trace_to(&mut outline, Point::new(top, first + i));
+        trace_to(&mut outline, Point::new(top, first + i + 1));
+    }
+    for (i, &(_, bottom)) in runs.iter().enumerate().rev() {
+        trace_to(&mut outline, Point::new(bottom, first + i + 1));
+        trace_to(&mut outline, Point::new(bottom, first + i));
+    }
+    trace_to(&mut outline, Point::new(runs[0].0, first));
+    outline.pop();
+    (outline, size)
+}
+
+fn random_field(rng: &mut Rng, size: usize) -> String {
+    let (outline, size) = random_loop(rng, size);
+    let mut tiles = Grid::new(size, size, '.');
+    for pos in tiles.positions().collect::<Vec<_>>() {
+        if rng.chance(1, 2) {
+            tiles[pos] = *rng.choose(&['|', '-', 'L', 'J', '7', 'F']);
+        }
+    }
+
+    let direction = |from: Point, to: Point| {
+        Direction::ALL
+            .into_iter()
+            .find(|&dir| from.checked_step(dir, 1) == Some(to))
+            .expect("the outline moves one tile at a time")
+    };
+    for (i, &pos) in outline.iter().enumerate() {
+        let prev = outline[(i + outline.len() - 1) % outline.len()];
+        let next = outline[(i + 1) % outline.len()];
+        let ends = [direction(pos, prev), direction(pos, next)];
+        tiles[pos] = "|-LJ7F"
+            .chars()
+            .find(|&tile| {
+                let pipe = pipe_ends(tile).unwrap();
+                ends.iter().all(|end| pipe.contains(end))
+            })
+            .expect("the outline never turns back on itself");
+    }
+
+    // the start's connections come from the pipes pointing at it, so no junk may
+    let n = outline.len();
+    let i = rng.index(0..n);
+    let (start, neighbours) = (outline[i], [outline[(i + n - 1) % n], outline[(i + 1) % n]]);
+    tiles[start] = 'S';
+    for dir in Direction::ALL {
+        let Some(next) = tiles.step(start, dir) else {
+            continue;
+        };
+        let points_at_start =
+            pipe_ends(tiles[next]).is_some_and(|ends| ends.contains(&dir.reverse()));
+        if points_at_start && !neighbours.contains(&next) {
+            tiles[next] = '.';
+        }
+    }
+    tiles.to_string()
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the field into a grid of
+/// tiles.
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Grid<char>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let tiles = Grid::parse(Self::DAY, input, "|-LJ7F.S")?;
+
+        let starts: Vec<_> = parse::records(Self::DAY, input)
+            .into_iter()
+            .flat_map(|line| line.text.match_indices('S').map(move |(j, s)| (line, j, s)))
+            .collect();
+        match starts.as_slice() {
+            [] => Err(parse::end_of_input(Self::DAY, input, "a start tile `S`")),
+            [_] => Ok(tiles),
+            [_, (line, j, s), ..] => {
+                Err(line.error(&line.text[*j..*j + s.len()], "a single start tile"))
+            }
+        }
+    }
+
+    fn part1(tiles: &Self::Input) -> usize {
+        farthest_distance(tiles)
+    }
+
+    fn part2(tiles: &Self::Input) -> usize {
+        enclosed_tiles(tiles)
+    }
+
+    fn render(tiles: &Self::Input) -> Option<Picture> {
+        Some(render_loop(tiles))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_field(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day10);
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day10::parse(".S.\n.x.\n").unwrap_err();
+        assert_eq!(err, ParseError::new(10, 2, 2, "one of `|-LJ7F.S`", "`x`"));
+
+        let err = Day10::parse("...\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(10, 2, 1, "a start tile `S`", "end of input")
+        );
+
+        let err = Day10::parse(".S.\n..S\n").unwrap_err();
+        assert_eq!(err, ParseError::new(10, 2, 3, "a single start tile", "`S`"));
+    }
+
+    #[test]
+    fn test_generate() {
+        for seed in 0..10 {
+            let mut rng = Rng::new(seed);
+            let tiles = Day10::parse(&random_field(&mut rng, 20)).unwrap();
+            let (outline, _) = random_loop(&mut Rng::new(seed), 20);
+            // the start is as far from the furthest tile in both directions
+            assert_eq!(Day10::part1(&tiles) * 2, outline.len());
+        }
+    }
+
+    #[test]
+    fn test_render_loop() {
+        let tiles = Day10::parse(include_str!("../fixtures/enclosed.txt")).unwrap();
+        let picture = render_loop(&tiles);
+        assert_eq!(picture.text(), &tiles);
+        let count = |class| {
+            picture
+                .classes()
+                .iter()
+                .filter(|(_, c)| **c == class)
+                .count()
+        };
+        assert_eq!(count(INSIDE), Day10::part2(&tiles));
+        assert_eq!(count(START), 1);
+        assert_eq!(count(LOOP) + 1, Day10::part1(&tiles) * 2);
+    }
+}
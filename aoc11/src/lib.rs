@@ -0,0 +1,205 @@
+//! Day 11: Cosmic Expansion
+//!
+//! Every empty row and column of the image is really several rows or
+//! columns wide. The answers are the sums of the distances between every
+//! pair of galaxies once the universe has expanded.
+
+use std::collections::HashSet;
+
+use aoc_common::render::{Class, Colour, Picture};
+use aoc_common::{parse, Grid, ParseError, Rng, Solution};
+
+fn parse_stars(lines: &[String]) -> Vec<(usize, usize)> {
+    let mut res: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        for (j, c) in line.as_bytes().iter().enumerate() {
+            if *c == b'#' {
+                res.push((i, j));
+            }
+        }
+    }
+    res
+}
+
+fn expand<F, FM>(
+    stars: &mut [(usize, usize)],
+    coord_accessor: F,
+    coord_accessor_mut: &mut FM,
+    factor: usize,
+) where
+    F: Fn(&(usize, usize)) -> &usize,
+    FM: FnMut(&mut (usize, usize)) -> &mut usize,
+{
+    stars.sort_by(|a, b| coord_accessor(a).cmp(coord_accessor(b)));
+    let mut shift: usize = 0;
+    let mut last: usize = 0;
+    for star in stars.iter_mut() {
+        let coord = coord_accessor_mut(star);
+        if let Some(diff) = coord.checked_sub(last + 1) {
+            if diff > 0 {
+                shift += diff * factor - 1;
+            }
+        }
+        last = *coord;
+        *coord += shift;
+    }
+}
+
+fn manhattan_dist(s1: &(usize, usize), s2: &(usize, usize)) -> usize {
+    let ((i1, j1), (i2, j2)) = ((s1.0 as i32, s1.1 as i32), (s2.0 as i32, s2.1 as i32));
+    ((i1 - i2).abs() + (j1 - j2).abs()) as usize
+}
+
+fn sum_dists(stars: &[(usize, usize)]) -> usize {
+    let mut dist: usize = 0;
+    for a in 0..stars.len() {
+        for b in a + 1..stars.len() {
+            dist += manhattan_dist(&stars[a], &stars[b]);
+        }
+    }
+    dist
+}
+
+/// The sum of the distances between every pair of galaxies at `(row, col)`
+/// once every empty row and column has grown to `factor` of them.
+pub fn expanded_distances(stars: &[(usize, usize)], factor: usize) -> usize {
+    let mut stars = stars.to_vec();
+    expand(&mut stars, |(i, _)| i, &mut |(i, _)| i, factor);
+    expand(&mut stars, |(_, j)| j, &mut |(_, j)| j, factor);
+    sum_dists(&stars)
+}
+
+const GALAXY: Class = Class::new("galaxy", Colour::rgb(255, 220, 120));
+const VOID: Class = Class::new("void", Colour::rgb(40, 40, 70));
+const SPACE: Class = Class::new("space", Colour::rgb(15, 15, 30));
+
+/// A picture of the image once every empty row and column has doubled, with
+/// the empty rows and columns shaded.
+pub fn render_expanded(stars: &[(usize, usize)]) -> Picture {
+    let mut stars = stars.to_vec();
+    expand(&mut stars, |(i, _)| i, &mut |(i, _)| i, 2);
+    expand(&mut stars, |(_, j)| j, &mut |(_, j)| j, 2);
+
+    let height = stars.iter().map(|&(i, _)| i + 1).max().unwrap_or(0);
+    let width = stars.iter().map(|&(_, j)| j + 1).max().unwrap_or(0);
+    let mut image = Grid::new(width, height, '.');
+    for &star in &stars {
+        image[star] = '#';
+    }
+
+    let rows: HashSet<usize> = stars.iter().map(|&(i, _)| i).collect();
+    let cols: HashSet<usize> = stars.iter().map(|&(_, j)| j).collect();
+    Picture::classify(image, |(i, j), c| {
+        if c == '#' {
+            GALAXY
+        } else if !rows.contains(&i) || !cols.contains(&j) {
+            VOID
+        } else {
+            SPACE
+        }
+    })
+}
+
+// ----------------------------------------------------------------------------
+// a square image with sparse galaxies, including a few empty rows and columns
+fn random_image(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(2);
+    let mut image = Grid::new(size, size, '.');
+    for pos in image.positions().collect::<Vec<_>>() {
+        if rng.chance(1, 20) {
+            image[pos] = '#';
+        }
+    }
+    for _ in 0..size / 10 {
+        let (row, col) = (rng.index(0..size), rng.index(0..size));
+        image.row_mut(row).fill('.');
+        for r in 0..size {
+            image[(r, col)] = '.';
+        }
+    }
+    image[(0, 0)] = '#';
+    image[(size - 1, size - 1)] = '#';
+    image.to_string()
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the image into the galaxies'
+/// `(row, col)` positions.
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Vec<(usize, usize)>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let lines = parse::grid(Self::DAY, input, ".#")?;
+        Ok(parse_stars(&lines))
+    }
+
+    fn part1(stars: &Self::Input) -> usize {
+        expanded_distances(stars, 2)
+    }
+
+    fn part2(stars: &Self::Input) -> usize {
+        expanded_distances(stars, 1000000)
+    }
+
+    fn render(stars: &Self::Input) -> Option<Picture> {
+        Some(render_expanded(stars))
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_image(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day11);
+
+    #[test]
+    fn test_solution2() {
+        let stars = Day11::parse(Day11::EXAMPLE).unwrap();
+        let result = expanded_distances(&stars, 10);
+        assert_eq!(result, 1030);
+        let result = expanded_distances(&stars, 100);
+        assert_eq!(result, 8410);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day11::parse("...#\n.*..\n").unwrap_err();
+        assert_eq!(err, ParseError::new(11, 2, 2, "one of `.#`", "`*`"));
+    }
+
+    #[test]
+    fn test_render_expanded() {
+        let stars = Day11::parse(Day11::EXAMPLE).unwrap();
+        let picture = render_expanded(&stars);
+        let expanded = "\
+....#........
+.........#...
+#............
+.............
+.............
+........#....
+.#...........
+............#
+.............
+.............
+.........#...
+#....#.......
+";
+        assert_eq!(picture.text().to_string(), expanded);
+        assert_eq!(picture.classes()[(3, 0)], VOID);
+        assert_eq!(picture.classes()[(0, 2)], VOID);
+        assert_eq!(picture.classes()[(0, 0)], SPACE);
+        assert_eq!(picture.classes()[(0, 4)], GALAXY);
+    }
+}
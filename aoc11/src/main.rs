@@ -1,133 +1,7 @@
-use std::fs::File;
-use std::io::{self, BufRead};
-use std::path::Path;
+use std::process::ExitCode;
 
-fn parse_stars(lines: &[String]) -> Vec<(usize, usize)> {
-    let mut res: Vec<(usize, usize)> = Vec::new();
-    for (i, line) in lines.iter().enumerate() {
-        for (j, c) in line.as_bytes().iter().enumerate() {
-            if *c == b'#' {
-                res.push((i, j));
-            }
-        }
-    }
-    res
-}
-
-fn expand<F, FM>(
-    stars: &mut Vec<(usize, usize)>,
-    coord_accessor: F,
-    coord_accessor_mut: &mut FM,
-    factor: usize,
-) where
-    F: Fn(&(usize, usize)) -> &usize,
-    FM: FnMut(&mut (usize, usize)) -> &mut usize,
-{
-    stars.sort_by(|a, b| coord_accessor(a).cmp(coord_accessor(b)));
-    let mut shift: usize = 0;
-    let mut last: usize = 0;
-    for star in stars.iter_mut() {
-        let coord = coord_accessor_mut(star);
-        if let Some(diff) = coord.checked_sub(last + 1) {
-            if diff > 0 {
-                shift += diff * factor - 1;
-            }
-        }
-        last = *coord;
-        *coord += shift;
-    }
-}
-
-fn manhattan_dist(s1: &(usize, usize), s2: &(usize, usize)) -> usize {
-    let ((i1, j1), (i2, j2)) = ((s1.0 as i32, s1.1 as i32), (s2.0 as i32, s2.1 as i32));
-    ((i1 - i2).abs() + (j1 - j2).abs()) as usize
-}
-
-fn sum_dists(stars: &Vec<(usize, usize)>) -> usize {
-    let mut dist: usize = 0;
-    for a in 0..stars.len() {
-        for b in a + 1..stars.len() {
-            dist += manhattan_dist(&stars[a], &stars[b]);
-        }
-    }
-    dist
-}
-
-fn solve(lines: &[String]) -> usize {
-    let mut stars = parse_stars(lines);
-    expand(&mut stars, |(i, _)| i, &mut |(i, _)| i, 2);
-    expand(&mut stars, |(_, j)| j, &mut |(_, j)| j, 2);
-    sum_dists(&stars)
-}
-
-// ----------------------------------------------------------------------------
-fn solve2(lines: &[String], factor: usize) -> usize {
-    let mut stars = parse_stars(lines);
-    expand(&mut stars, |(i, _)| i, &mut |(i, _)| i, factor);
-    expand(&mut stars, |(_, j)| j, &mut |(_, j)| j, factor);
-    sum_dists(&stars)
-}
-
-// ----------------------------------------------------------------------------
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_solution1() {
-        let input = r"...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....
-";
-        let lines: Vec<String> = input.lines().map(|line| line.to_string()).collect();
-        let result = solve(&lines);
-        assert_eq!(result, 374);
-    }
-
-    #[test]
-    fn test_solution2() {
-        let input = r"...#......
-.......#..
-#.........
-..........
-......#...
-.#........
-.........#
-..........
-.......#..
-#...#.....
-";
-        let lines: Vec<String> = input.lines().map(|line| line.to_string()).collect();
-        let result = solve2(&lines, 10);
-        assert_eq!(result, 1030);
-        let result = solve2(&lines, 100);
-        assert_eq!(result, 8410);
-    }
-}
-
-// ----------------------------------------------------------------------------
-fn read_lines<P>(filename: P) -> io::Result<Vec<String>>
-where
-    P: AsRef<Path>,
-{
-    let file = File::open(filename)?;
-    let buf_reader = io::BufReader::new(file);
-    buf_reader.lines().collect()
-}
-
-fn main() -> io::Result<()> {
-    let filename = "src/input.in";
-    let lines = read_lines(filename)?;
-
-    println!("Answer, part 1: {}", solve(&lines));
-    println!("Answer, part 2: {}", solve2(&lines, 1000000));
+use aoc11::Day11;
 
-    Ok(())
+fn main() -> ExitCode {
+    aoc_common::run::<Day11>(concat!(env!("CARGO_MANIFEST_DIR"), "/src/input.in"))
 }
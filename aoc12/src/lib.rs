@@ -0,0 +1,284 @@
+//! Day 12: Hot Springs
+//!
+//! Every row of springs is a pattern of operational `.`, damaged `#` and
+//! unknown `?` springs, plus the sizes of the groups of damaged springs.
+//! The answers count the ways to fill in the unknown springs, for the rows
+//! as given and for the rows unfolded five times.
+
+use std::collections::HashMap;
+
+use aoc_common::parse::{self, Line};
+use aoc_common::{debug, trace, ParseError, Rng, Solution};
+
+fn parse_line(line: &Line) -> Result<(String, Vec<usize>), ParseError> {
+    let (pattern, counts) = line.split_once(line.text, ' ')?;
+    if let Some((i, c)) = pattern.char_indices().find(|(_, c)| !".#?".contains(*c)) {
+        return Err(line.error(&pattern[i..i + c.len_utf8()], "one of `.#?`"));
+    }
+    let consec_springs: Vec<usize> = counts
+        .split(',')
+        .map(|s| line.parse(s, "a group size"))
+        .collect::<Result<_, _>>()?;
+    Ok((pattern.to_string(), consec_springs))
+}
+
+/// The number of ways to replace every `?` in `pattern` with `.` or `#` so
+/// that its runs of `#` have the sizes in `consec_springs`, in order.
+pub fn num_arrangements(pattern: &str, consec_springs: &[usize]) -> usize {
+    let mut cache: HashMap<(usize, usize), usize> = HashMap::new();
+    let arrangements = num_arrangements_memoised(pattern, consec_springs, (0, 0), &mut cache);
+    debug!(
+        "{} {:?}: {} arrangements, {} memoised",
+        pattern,
+        consec_springs,
+        arrangements,
+        cache.len()
+    );
+    arrangements
+}
+
+fn num_arrangements_memoised(
+    pattern: &str,
+    consec_springs: &[usize],
+    (offset_p, offset_c): (usize, usize),
+    cache: &mut HashMap<(usize, usize), usize>,
+) -> usize {
+    if let Some(memoised) = cache.get(&(offset_p, offset_c)) {
+        trace!(
+            "memo hit at offset {}, group {}: {}",
+            offset_p,
+            offset_c,
+            memoised
+        );
+        return *memoised;
+    }
+
+    if offset_c == consec_springs.len() {
+        return if offset_p >= pattern.len()
+            || pattern[offset_p..].chars().all(|c| c == '.' || c == '?')
+        {
+            1
+        } else {
+            0
+        };
+    }
+    if offset_p >= pattern.len() {
+        return 0;
+    }
+
+    let result = (|| {
+        let curr_c = pattern.chars().nth(offset_p).unwrap();
+        if curr_c == '.' {
+            return num_arrangements_memoised(
+                pattern,
+                consec_springs,
+                (offset_p + 1, offset_c),
+                cache,
+            );
+        }
+
+        let consec_width = consec_springs[offset_c];
+        let pattern_end = offset_p + consec_width;
+        if pattern_end > pattern.len() {
+            return 0;
+        }
+
+        let mut result = 0;
+
+        if curr_c == '?' {
+            result +=
+                num_arrangements_memoised(pattern, consec_springs, (offset_p + 1, offset_c), cache);
+        }
+
+        if pattern[offset_p..pattern_end]
+            .chars()
+            .all(|c| c == '#' || c == '?')
+            && (pattern_end < pattern.len()
+                && *pattern.as_bytes().get(pattern_end).unwrap() != b'#'
+                || pattern_end == pattern.len())
+        {
+            result += num_arrangements_memoised(
+                pattern,
+                consec_springs,
+                (pattern_end + 1, offset_c + 1),
+                cache,
+            )
+        }
+
+        result
+    })();
+
+    cache.insert((offset_p, offset_c), result);
+    result
+}
+
+// ----------------------------------------------------------------------------
+/// Unfolds a row: five copies of the pattern joined by `?`, and five copies
+/// of the group sizes.
+pub fn extend_map((line, springs): (String, Vec<usize>)) -> (String, Vec<usize>) {
+    let mut pattern = line.clone();
+    let mut consec_springs = springs.clone();
+
+    for _ in 1..5 {
+        pattern = format!("{}?{}", pattern, line);
+        consec_springs.extend_from_slice(&springs);
+    }
+
+    (pattern, consec_springs)
+}
+
+// ----------------------------------------------------------------------------
+// every row starts out as a real arrangement before some springs are hidden,
+// so it has at least one
+fn random_records(rng: &mut Rng, rows: usize) -> String {
+    let mut records = String::new();
+    for _ in 0..rows {
+        let mut springs: Vec<char> = (0..rng.index(1..21))
+            .map(|_| if rng.chance(1, 2) { '#' } else { '.' })
+            .collect();
+        let at = rng.index(0..springs.len());
+        springs[at] = '#';
+        let groups: Vec<String> = springs
+            .split(|&c| c == '.')
+            .filter(|group| !group.is_empty())
+            .map(|group| group.len().to_string())
+            .collect();
+        let pattern: String = springs
+            .iter()
+            .map(|&c| if rng.chance(1, 3) { '?' } else { c })
+            .collect();
+        records += &format!("{} {}\n", pattern, groups.join(","));
+    }
+    records
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the records into
+/// `(pattern, group sizes)` rows.
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Vec<(String, Vec<usize>)>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::records(Self::DAY, input)
+            .iter()
+            .map(parse_line)
+            .collect()
+    }
+
+    fn part1(rows: &Self::Input) -> usize {
+        rows.iter().map(|(p, c)| num_arrangements(p, c)).sum()
+    }
+
+    fn part2(rows: &Self::Input) -> usize {
+        rows.iter()
+            .cloned()
+            .map(extend_map)
+            .map(|(p, c)| num_arrangements(&p, &c))
+            .sum()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_records(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::property;
+
+    aoc_common::fixture_tests!(Day12);
+
+    // tries every way to fill in the `?`s and keeps those with the right groups
+    fn brute_force_arrangements(pattern: &str, groups: &[usize]) -> usize {
+        let unknown: Vec<usize> = pattern.match_indices('?').map(|(i, _)| i).collect();
+        (0..1u32 << unknown.len())
+            .filter(|mask| {
+                let mut springs = pattern.as_bytes().to_vec();
+                for (bit, &i) in unknown.iter().enumerate() {
+                    springs[i] = if mask >> bit & 1 == 1 { b'#' } else { b'.' };
+                }
+                springs
+                    .split(|&c| c == b'.')
+                    .filter(|group| !group.is_empty())
+                    .map(|group| group.len())
+                    .eq(groups.iter().copied())
+            })
+            .count()
+    }
+
+    fn random_row(rng: &mut Rng) -> (String, Vec<usize>) {
+        let pattern = (0..rng.index(1..13))
+            .map(|_| *rng.choose(&['.', '#', '?', '?']))
+            .collect();
+        let groups = (0..rng.index(1..5)).map(|_| rng.index(1..5)).collect();
+        (pattern, groups)
+    }
+
+    // springs shrink to `.`, and there is always at least one group of one
+    fn shrink_row((pattern, groups): &(String, Vec<usize>)) -> Vec<(String, Vec<usize>)> {
+        let springs: Vec<char> = pattern.chars().collect();
+        let patterns =
+            property::shrink_vec(&springs, |&c| if c == '.' { vec![] } else { vec!['.'] });
+        let group_lists = property::shrink_vec(groups, |&size| {
+            property::shrink_number(size as u64 - 1)
+                .into_iter()
+                .map(|size| size as usize + 1)
+                .collect()
+        });
+        patterns
+            .into_iter()
+            .map(|springs| (springs.into_iter().collect(), groups.clone()))
+            .chain(
+                group_lists
+                    .into_iter()
+                    .filter(|groups| !groups.is_empty())
+                    .map(|groups| (pattern.clone(), groups)),
+            )
+            .collect()
+    }
+
+    #[test]
+    fn test_num_arrangements_matches_brute_force() {
+        property::equivalent(
+            property::CASES,
+            random_row,
+            shrink_row,
+            |(pattern, groups)| num_arrangements(pattern, groups),
+            |(pattern, groups)| brute_force_arrangements(pattern, groups),
+        );
+    }
+
+    #[test]
+    fn test_num_arrangements() {
+        assert_eq!(num_arrangements("??.??", &[1, 1]), 4);
+    }
+
+    #[test]
+    fn test_parse_line2() {
+        let (pattern, _) = extend_map(parse_line(&parse::lines(12, "???.### 1,1,3")[0]).unwrap());
+        assert_eq!(pattern, "???.###????.###????.###????.###????.###");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day12::parse("???.### 1,1,3\n.??x 1\n").unwrap_err();
+        assert_eq!(err, ParseError::new(12, 2, 4, "one of `.#?`", "`x`"));
+
+        let err = Day12::parse("???.### 1,,3\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(12, 1, 11, "a group size", "end of line")
+        );
+
+        let err = Day12::parse("???.###\n").unwrap_err();
+        assert_eq!(err, ParseError::new(12, 1, 8, "` `", "end of line"));
+    }
+}
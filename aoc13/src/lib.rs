@@ -0,0 +1,256 @@
+//! Day 13: Point of Incidence
+//!
+//! Every pattern of ash `.` and rocks `#` has a line of reflection between
+//! two rows or two columns. In part 2, exactly one cell of every pattern is
+//! a smudge whose fix makes for a different line.
+
+use std::collections::HashSet;
+
+use aoc_common::parse::{self, Section};
+use aoc_common::{Grid, ParseError, Rng, Solution};
+
+fn parse_mirror(section: Section) -> Result<Grid<char>, ParseError> {
+    parse::check_grid(&section.lines, ".#")?;
+    Ok(Grid::from_rows(
+        section
+            .lines
+            .iter()
+            .map(|line| line.text.chars().collect())
+            .collect(),
+    ))
+}
+
+fn mirrored(line: &[char], pivot: usize) -> bool {
+    let mut j = pivot;
+    let mut k = pivot + 1;
+    loop {
+        if line[j] != line[k] {
+            break false;
+        }
+        if j == 0 || k == line.len() - 1 {
+            break true;
+        }
+        j -= 1;
+        k += 1;
+    }
+}
+
+fn mirrored_i(mirror: &Grid<char>, j: usize, pivot: usize) -> bool {
+    let mut i = pivot;
+    let mut k = pivot + 1;
+    loop {
+        if mirror[(i, j)] != mirror[(k, j)] {
+            break false;
+        }
+        if i == 0 || k == mirror.height() - 1 {
+            break true;
+        }
+        i -= 1;
+        k += 1;
+    }
+}
+
+fn vertical(mirror: &Grid<char>, banned: usize) -> usize {
+    let mut axes: HashSet<usize> = (0..mirror.width() - 1).collect();
+    axes.remove(&banned);
+    let mut i: usize = 0;
+    while i < mirror.height() && !axes.is_empty() {
+        let axes_at_i = axes.clone();
+        for j in axes_at_i {
+            if !mirrored(mirror.row(i), j) {
+                axes.remove(&j);
+            }
+        }
+        i += 1;
+    }
+
+    if axes.is_empty() {
+        0
+    } else {
+        *axes.iter().next().unwrap() + 1
+    }
+}
+
+fn horizontal(mirror: &Grid<char>, banned: usize) -> usize {
+    let mut axes: HashSet<usize> = (0..mirror.height() - 1).collect();
+    axes.remove(&banned);
+    let mut j: usize = 0;
+    while j < mirror.width() && !axes.is_empty() {
+        let axes_at_j = axes.clone();
+        for i in axes_at_j {
+            if !mirrored_i(mirror, j, i) {
+                axes.remove(&i);
+            }
+        }
+        j += 1;
+    }
+
+    if axes.is_empty() || axes.len() > 1 {
+        0
+    } else {
+        *axes.iter().next().unwrap() + 1
+    }
+}
+
+/// The summary of a pattern's line of reflection: the number of columns to
+/// its left, or a hundred times the number of rows above it.
+pub fn calc_reflection(mirror: &Grid<char>) -> usize {
+    vertical(mirror, 1000) + horizontal(mirror, 1000) * 100
+}
+
+// ----------------------------------------------------------------------------
+/// Like [`calc_reflection`], for the new line of reflection once the one
+/// smudge is fixed, or 0 if no single fix makes one.
+pub fn calc_reflection_smudged(mirror: &Grid<char>) -> usize {
+    let (ov, oh) = (vertical(mirror, 1000), horizontal(mirror, 1000));
+    let (ov, oh) = (
+        if ov > 0 { ov } else { 1000 },
+        if oh > 0 { oh } else { 1000 },
+    );
+    let flip = |c| if c == '.' { '#' } else { '.' };
+
+    let mut canvas = mirror.clone();
+
+    for pos in mirror.positions() {
+        canvas[pos] = flip(canvas[pos]);
+        let (nv, nh) = (vertical(&canvas, ov - 1), horizontal(&canvas, oh - 1));
+        canvas[pos] = flip(canvas[pos]);
+
+        if nv > 0 || nh > 0 {
+            return nv + nh * 100;
+        }
+    }
+
+    0
+}
+
+// ----------------------------------------------------------------------------
+// the number of cells that differ between the rows mirrored around the line
+// above row `axis`
+fn row_mismatches(pattern: &Grid<char>, axis: usize) -> usize {
+    (0..axis.min(pattern.height() - axis))
+        .map(|k| {
+            let (above, below) = (pattern.row(axis - 1 - k), pattern.row(axis + k));
+            above.iter().zip(below).filter(|(a, b)| a != b).count()
+        })
+        .sum()
+}
+
+// how many mirror lines in either direction have exactly `mismatches` cells
+// out of place
+fn count_lines(pattern: &Grid<char>, mismatches: usize) -> usize {
+    let transposed = pattern.transpose();
+    let count = |grid: &Grid<char>| {
+        (1..grid.height())
+            .filter(|&axis| row_mismatches(grid, axis) == mismatches)
+            .count()
+    };
+    count(pattern) + count(&transposed)
+}
+
+// A pattern with one perfect vertical mirror line and one horizontal line
+// that is a single smudge away from being one, possibly transposed. The
+// smudge sits in a column that the vertical mirror leaves out.
+fn random_pattern(rng: &mut Rng) -> Grid<char> {
+    loop {
+        let (width, height) = (rng.index(5..16), rng.index(5..16));
+        let mut pattern = Grid::new(width, height, '.');
+        for pos in pattern.positions().collect::<Vec<_>>() {
+            if rng.chance(1, 2) {
+                pattern[pos] = '#';
+            }
+        }
+
+        let smudged = rng.index(1..height);
+        for k in 0..smudged.min(height - smudged) {
+            let above = pattern.row(smudged - 1 - k).to_vec();
+            pattern.row_mut(smudged + k).copy_from_slice(&above);
+        }
+        let mirror = rng.index(1..width);
+        if mirror * 2 == width {
+            continue;
+        }
+        let reach = mirror.min(width - mirror);
+        for row in 0..height {
+            for k in 0..reach {
+                pattern[(row, mirror + k)] = pattern[(row, mirror - 1 - k)];
+            }
+        }
+
+        let free: Vec<usize> = (0..width)
+            .filter(|&col| col + reach < mirror || col >= mirror + reach)
+            .collect();
+        let row = smudged - 1 - rng.index(0..smudged.min(height - smudged));
+        let col = *rng.choose(&free);
+        pattern[(row, col)] = if pattern[(row, col)] == '#' { '.' } else { '#' };
+
+        if count_lines(&pattern, 0) == 1 && count_lines(&pattern, 1) == 1 {
+            return if rng.chance(1, 2) {
+                pattern.transpose()
+            } else {
+                pattern
+            };
+        }
+    }
+}
+
+fn random_patterns(rng: &mut Rng, patterns: usize) -> String {
+    let patterns: Vec<String> = (0..patterns.max(1))
+        .map(|_| random_pattern(rng).to_string())
+        .collect();
+    patterns.join("\n")
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the notes into patterns.
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Vec<Grid<char>>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse::sections(Self::DAY, input)
+            .map(parse_mirror)
+            .collect()
+    }
+
+    fn part1(mirrors: &Self::Input) -> usize {
+        mirrors.iter().map(calc_reflection).sum()
+    }
+
+    fn part2(mirrors: &Self::Input) -> usize {
+        mirrors.iter().map(calc_reflection_smudged).sum()
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_patterns(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day13);
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day13::parse("#.#\n.#.\n\n##\n#.#\n").unwrap_err();
+        assert_eq!(err, ParseError::new(13, 5, 3, "a row of width 2", "`#`"));
+    }
+
+    #[test]
+    fn test_generate() {
+        let mut rng = Rng::new(1);
+        for _ in 0..20 {
+            let pattern = random_pattern(&mut rng);
+            let (clean, smudged) = (calc_reflection(&pattern), calc_reflection_smudged(&pattern));
+            assert!(clean > 0 && smudged > 0 && clean != smudged);
+        }
+    }
+}
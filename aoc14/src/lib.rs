@@ -0,0 +1,318 @@
+//! Day 14: Parabolic Reflector Dish
+//!
+//! The platform holds round rocks `O`, which roll when it is tilted, and
+//! cube-shaped rocks `#`, which stay put. The load on the north beams is
+//! wanted after tilting north once, and after a billion spin cycles of
+//! tilting north, west, south and east.
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+
+use aoc_common::animate::Screen;
+use aoc_common::cycle::History;
+use aoc_common::render::{Class, Colour, Picture};
+use aoc_common::{debug, trace, CancellationToken, Cancelled, Grid, ParseError, Rng, Solution};
+
+/// The platform's tiles.
+pub type Canvas = Grid<char>;
+
+fn tilt_row(row: &mut [char], cache: &mut HashMap<Vec<char>, Vec<char>>) {
+    if let Some(memoised) = cache.get(row) {
+        trace!("tilt_row cache hit for {}", row.iter().collect::<String>());
+        row.copy_from_slice(memoised);
+        return;
+    }
+
+    let key = row.to_vec();
+
+    let mut i = 0;
+    while i < row.len() {
+        let begin_it = i;
+        let mut n_stones = 0;
+
+        // find how many movable rows we have
+        while i < row.len() {
+            match row[i] {
+                'O' => n_stones += 1,
+                '#' => break,
+                _ => {}
+            }
+            i += 1;
+        }
+
+        // fill out stones followed by empty spaces
+        row[begin_it..begin_it + n_stones].fill('O');
+        row[begin_it + n_stones..i].fill('.');
+
+        i += 1;
+    }
+
+    cache.insert(key, row.to_vec());
+}
+
+fn tilt(canvas: &mut Canvas, cache: &mut HashMap<Vec<char>, Vec<char>>) {
+    for i in 0..canvas.height() {
+        tilt_row(canvas.row_mut(i), cache);
+    }
+}
+
+/// The platform once every round rock has rolled as far north as it can.
+pub fn tilt_north(canvas: &Canvas) -> Canvas {
+    let mut canvas = canvas.rotate_ccw();
+    tilt(&mut canvas, &mut Default::default());
+    canvas.rotate_cw()
+}
+
+// ----------------------------------------------------------------------------
+fn tilt_full(canvas: &mut Canvas, cache: &mut HashMap<Vec<char>, Vec<char>>) {
+    *canvas = canvas.rotate_ccw();
+    tilt(canvas, cache);
+    *canvas = canvas.rotate_cw();
+    tilt(canvas, cache);
+    *canvas = canvas.rotate_cw();
+    tilt(canvas, cache);
+    *canvas = canvas.rotate_cw();
+    tilt(canvas, cache);
+    *canvas = canvas.rotate_cw().rotate_cw();
+}
+
+/// The load on the north beams: every round rock counts its distance from
+/// the south edge.
+pub fn north_load(canvas: &Canvas) -> usize {
+    canvas
+        .iter()
+        .filter(|(_, c)| **c == 'O')
+        .map(|((i, _), _)| canvas.height() - i)
+        .sum()
+}
+
+/// The load on the north beams after a billion spin cycles.
+///
+/// Spins until the canvas repeats, then skips ahead to the billionth cycle.
+pub fn spin_cycles(lines: &Canvas, token: &CancellationToken) -> Result<usize, Cancelled> {
+    let mut canvas = lines.clone();
+    let mut cache: HashMap<Vec<char>, Vec<char>> = Default::default();
+    let mut history = History::new();
+
+    let cycle = loop {
+        token.check()?;
+        if let Some(cycle) = history.push(canvas.clone()) {
+            break cycle;
+        }
+        tilt_full(&mut canvas, &mut cache);
+    };
+    debug!(
+        "spin cycle repeats every {} cycles after {}, {} rows cached",
+        cycle.length,
+        cycle.prefix,
+        cache.len()
+    );
+
+    Ok(north_load(history.nth(&cycle, 1000000000)))
+}
+
+const ROUND: Class = Class::new("round", Colour::rgb(120, 170, 230));
+const CUBE: Class = Class::new("cube", Colour::rgb(90, 90, 90));
+const VACATED: Class = Class::new("vacated", Colour::rgb(250, 215, 160));
+const EMPTY: Class = Class::new("empty", Colour::rgb(240, 240, 240));
+
+/// A picture of the platform tilted north, with the tiles that round rocks
+/// rolled away from marked.
+pub fn render_tilt(canvas: &Canvas) -> Picture {
+    let tilted = tilt_north(canvas);
+    Picture::classify(tilted, |pos, tile| match (tile, canvas[pos]) {
+        ('O', _) => ROUND,
+        ('#', _) => CUBE,
+        (_, 'O') => VACATED,
+        _ => EMPTY,
+    })
+}
+
+fn platform_frame(canvas: &Canvas) -> Picture {
+    Picture::classify(canvas.clone(), |_, tile| match tile {
+        'O' => ROUND,
+        '#' => CUBE,
+        _ => EMPTY,
+    })
+}
+
+/// Plays the spin cycles on `screen`, one frame per tilt, until the platform
+/// comes back to a state it has been in before.
+pub fn animate_spin(canvas: &Canvas, screen: &mut dyn Screen) -> ControlFlow<()> {
+    // how to turn the platform so that each side in turn is west, and back
+    type Turn = fn(&Canvas) -> Canvas;
+    let tilts: [(&str, Turn, Turn); 4] = [
+        ("north", Grid::rotate_ccw, Grid::rotate_cw),
+        ("west", Canvas::clone, Canvas::clone),
+        ("south", Grid::rotate_cw, Grid::rotate_ccw),
+        (
+            "east",
+            |c| c.rotate_cw().rotate_cw(),
+            |c| c.rotate_cw().rotate_cw(),
+        ),
+    ];
+
+    let mut canvas = canvas.clone();
+    let mut cache: HashMap<Vec<char>, Vec<char>> = Default::default();
+    let mut history = History::new();
+    screen.show(&platform_frame(&canvas), "start")?;
+    for n in 1.. {
+        if history.push(canvas.clone()).is_some() {
+            break;
+        }
+        for (side, turn, turn_back) in tilts {
+            let mut turned = turn(&canvas);
+            tilt(&mut turned, &mut cache);
+            canvas = turn_back(&turned);
+            let caption = format!("cycle {}, tilted {}", n, side);
+            screen.show(&platform_frame(&canvas), &caption)?;
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+// ----------------------------------------------------------------------------
+fn random_platform(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut platform = Grid::new(size, size, '.');
+    for pos in platform.positions().collect::<Vec<_>>() {
+        platform[pos] = match rng.below(30) {
+            0..=5 => 'O',
+            6..=10 => '#',
+            _ => '.',
+        };
+    }
+    platform.to_string()
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the platform into a
+/// [`Canvas`].
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Canvas;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(Self::DAY, input, ".#O")
+    }
+
+    fn part1(lines: &Self::Input) -> usize {
+        north_load(&tilt_north(lines))
+    }
+
+    fn part2(lines: &Self::Input) -> usize {
+        spin_cycles(lines, &CancellationToken::new()).expect("the token is never cancelled")
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_platform(rng, size)
+    }
+
+    fn render(lines: &Self::Input) -> Option<Picture> {
+        Some(render_tilt(lines))
+    }
+
+    fn animate(lines: &Self::Input, screen: &mut dyn Screen) -> bool {
+        let _ = animate_spin(lines, screen);
+        true
+    }
+
+    fn part2_cancellable(
+        lines: &Self::Input,
+        token: &CancellationToken,
+    ) -> Result<usize, Cancelled> {
+        spin_cycles(lines, token)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day14);
+
+    #[test]
+    fn test_full_cycle() {
+        let mut canvas = Day14::parse(Day14::EXAMPLE).unwrap();
+        let mut cache: HashMap<Vec<char>, Vec<char>> = Default::default();
+
+        tilt_full(&mut canvas, &mut cache);
+
+        let output = r".....#....
+....#...O#
+...OO##...
+.OO#......
+.....OOO#.
+.O#...O#.#
+....O#....
+......OOOO
+#...O###..
+#..OO#....
+";
+        assert_eq!(canvas.to_string(), output);
+    }
+
+    #[test]
+    fn test_rectangle() {
+        let canvas = Day14::parse("....\nO#.O\n").unwrap();
+        assert_eq!(tilt_north(&canvas).to_string(), "O..O\n.#..\n");
+        assert_eq!(Day14::part1(&canvas), 4);
+        // one spin cycle leaves `...O` over `O#..`, which every later cycle repeats
+        assert_eq!(Day14::part2(&canvas), 3);
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let canvas = Day14::parse(Day14::EXAMPLE).unwrap();
+        let token = CancellationToken::new();
+        token.cancel();
+        assert_eq!(Day14::part2_cancellable(&canvas, &token), Err(Cancelled));
+    }
+
+    #[derive(Default)]
+    struct Frames(Vec<(String, String)>);
+
+    impl Screen for Frames {
+        fn show(&mut self, frame: &Picture, caption: &str) -> ControlFlow<()> {
+            self.0.push((caption.to_string(), frame.text().to_string()));
+            ControlFlow::Continue(())
+        }
+    }
+
+    #[test]
+    fn test_animate_spin() {
+        let canvas = Day14::parse(Day14::EXAMPLE).unwrap();
+        let mut frames = Frames::default();
+        assert!(Day14::animate(&canvas, &mut frames));
+
+        let (caption, first) = &frames.0[1];
+        assert_eq!(caption, "cycle 1, tilted north");
+        assert_eq!(first, &tilt_north(&canvas).to_string());
+
+        let mut cycled = canvas.clone();
+        tilt_full(&mut cycled, &mut Default::default());
+        let (caption, last) = &frames.0[4];
+        assert_eq!(caption, "cycle 1, tilted east");
+        assert_eq!(last, &cycled.to_string());
+
+        // the example repeats from cycle 3 on, every 7 cycles
+        assert_eq!(frames.0.len(), 1 + 10 * 4);
+    }
+
+    #[test]
+    fn test_render_tilt() {
+        let canvas = Day14::parse("O.\n.O\n").unwrap();
+        let picture = render_tilt(&canvas);
+        assert_eq!(picture.text().to_string(), "OO\n..\n");
+        assert_eq!(
+            picture.classes(),
+            &Grid::from_rows(vec![vec![ROUND, ROUND], vec![EMPTY, VACATED]])
+        );
+    }
+}
@@ -0,0 +1,173 @@
+//! Day 15: Lens Library
+//!
+//! The initialization sequence is a list of steps that each insert a lens
+//! into, or remove one from, one of 256 boxes, picked by the HASH of the
+//! step's label.
+
+use aoc_common::parse::{self, Line};
+use aoc_common::{ParseError, Rng, Solution};
+
+/// What a step does to the box its label hashes to.
+#[derive(Debug)]
+pub enum Operation {
+    /// Takes out the lens with the label, if there is one.
+    Remove,
+    /// Puts in a lens with this focal length, replacing any with the label.
+    Insert(usize),
+}
+
+/// One step of the initialization sequence.
+#[derive(Debug)]
+pub struct Step {
+    /// The step as written, such as `rn=1`.
+    pub text: String,
+    pub label: String,
+    pub operation: Operation,
+}
+
+fn parse_step(line: &Line, text: &str) -> Result<Step, ParseError> {
+    let (label, operation) = if let Some(label) = text.strip_suffix('-') {
+        (label, Operation::Remove)
+    } else if let Some((label, focal_length)) = text.split_once('=') {
+        (
+            label,
+            Operation::Insert(line.parse(focal_length, "a focal length")?),
+        )
+    } else {
+        return Err(line.error(&text[text.len()..], "`=` or `-`"));
+    };
+    if label.is_empty() {
+        return Err(line.error(text, "a label"));
+    }
+    Ok(Step {
+        text: text.to_string(),
+        label: label.to_string(),
+        operation,
+    })
+}
+
+/// The HASH algorithm: a value from 0 to 255 for any text.
+pub fn ascii_hash(text: &str) -> usize {
+    text.as_bytes()
+        .iter()
+        .fold(0, |acc, &c| (acc + c as usize) * 17 % 256)
+}
+
+/// The total focusing power of the lenses once every step has been carried
+/// out.
+pub fn focusing_power(steps: &[Step]) -> usize {
+    let mut boxes: Vec<Vec<(String, usize)>> = vec![vec![]; 256];
+
+    for step in steps {
+        let tag = &step.label;
+        let box_idx = ascii_hash(tag);
+        match step.operation {
+            Operation::Insert(fl) => {
+                if let Some((_, stored_fl)) = boxes[box_idx]
+                    .iter_mut()
+                    .find(|(stored_tag, _)| stored_tag == tag)
+                {
+                    *stored_fl = fl;
+                } else {
+                    boxes[box_idx].push((tag.to_string(), fl));
+                }
+            }
+            Operation::Remove => boxes[box_idx].retain(|(stored_tag, _)| stored_tag != tag),
+        }
+    }
+
+    let calc_box = |idx, b: &Vec<(String, usize)>| -> usize {
+        b.iter()
+            .enumerate()
+            .map(|(b_idx, (_, fl))| (idx + 1) * (b_idx + 1) * fl)
+            .sum()
+    };
+
+    boxes
+        .iter()
+        .enumerate()
+        .map(|(idx, b)| calc_box(idx, b))
+        .sum()
+}
+
+// ----------------------------------------------------------------------------
+// labels are drawn from a small pool so that steps replace and remove lenses
+fn random_sequence(rng: &mut Rng, steps: usize) -> String {
+    let letters: Vec<char> = ('a'..='z').collect();
+    let labels: Vec<String> = (0..steps / 3 + 1)
+        .map(|_| {
+            (0..rng.index(2..7))
+                .map(|_| *rng.choose(&letters))
+                .collect()
+        })
+        .collect();
+    let steps: Vec<String> = (0..steps.max(1))
+        .map(|_| {
+            let label = rng.choose(&labels);
+            if rng.chance(1, 3) {
+                format!("{}-", label)
+            } else {
+                format!("{}={}", label, rng.range(1..10))
+            }
+        })
+        .collect();
+    steps.join(",") + "\n"
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the initialization sequence
+/// into its [`Step`]s.
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Vec<Step>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let lines = parse::records(Self::DAY, input);
+        let Some(sequence) = lines.first() else {
+            return Err(parse::end_of_input(
+                Self::DAY,
+                input,
+                "an initialization sequence",
+            ));
+        };
+        sequence
+            .text
+            .split(',')
+            .map(|step| parse_step(sequence, step))
+            .collect()
+    }
+
+    fn part1(steps: &Self::Input) -> usize {
+        steps.iter().map(|s| ascii_hash(&s.text)).sum()
+    }
+
+    fn part2(steps: &Self::Input) -> usize {
+        focusing_power(steps)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_sequence(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day15);
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day15::parse("rn=1,cm=x,qp-").unwrap_err();
+        assert_eq!(err, ParseError::new(15, 1, 9, "a focal length", "`x`"));
+
+        let err = Day15::parse("rn=1,cm").unwrap_err();
+        assert_eq!(err, ParseError::new(15, 1, 8, "`=` or `-`", "end of line"));
+    }
+}
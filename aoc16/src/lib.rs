@@ -0,0 +1,233 @@
+//! Day 16: The Floor Will Be Lava
+//!
+//! A beam of light enters the contraption and bounces off its mirrors `/`
+//! and `\` and splits at its splitters `|` and `-`. The tiles it passes
+//! through are energised.
+
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use aoc_common::animate::Screen;
+use aoc_common::render::{Class, Colour, Picture};
+use aoc_common::search::{self, Graph};
+use aoc_common::{Direction, Grid, ParseError, Point, Rng, Solution};
+
+/// A beam of light: the tile it is on and the direction it is heading.
+pub type Beam = (Point, Direction);
+
+struct Contraption<'a>(&'a Grid<char>);
+
+impl Graph for Contraption<'_> {
+    type State = Beam;
+
+    // beams that would leave the grid are dropped
+    fn neighbours(&self, &(pos, dir): &Beam) -> Vec<Beam> {
+        let grid = self.0;
+        let dirs = match dir.split(grid[pos]) {
+            Some(split) => split.to_vec(),
+            None => vec![dir.reflect(grid[pos])],
+        };
+        dirs.into_iter()
+            .filter_map(|next| grid.step(pos, next).map(|p| (p, next)))
+            .collect()
+    }
+}
+
+/// The tiles energised by a beam that starts out as `init`.
+pub fn detect_energised(grid: &Grid<char>, init: Beam) -> HashSet<Point> {
+    let beams = search::bfs(&Contraption(grid), [init], |_, _| ControlFlow::Continue(()));
+    beams.distances().map(|((pos, _), _)| *pos).collect()
+}
+
+/// The most tiles a beam can energise when it enters from any edge.
+pub fn max_energised(grid: &Grid<char>) -> usize {
+    let mut max_energised = 0;
+    for i in 0..grid.height() {
+        for j in 0..grid.width() {
+            if i == 0 {
+                max_energised = std::cmp::max(
+                    max_energised,
+                    detect_energised(grid, (Point::new(i, j), Direction::S)).len(),
+                );
+            }
+            if j == 0 {
+                max_energised = std::cmp::max(
+                    max_energised,
+                    detect_energised(grid, (Point::new(i, j), Direction::E)).len(),
+                );
+            }
+            if i == grid.height() - 1 {
+                max_energised = std::cmp::max(
+                    max_energised,
+                    detect_energised(grid, (Point::new(i, j), Direction::N)).len(),
+                );
+            }
+            if j == grid.width() - 1 {
+                max_energised = std::cmp::max(
+                    max_energised,
+                    detect_energised(grid, (Point::new(i, j), Direction::W)).len(),
+                );
+            }
+        }
+    }
+    max_energised
+}
+
+const ENERGISED: Class = Class::new("energised", Colour::rgb(255, 200, 70));
+const DARK: Class = Class::new("dark", Colour::rgb(60, 60, 80));
+
+/// A picture of the contraption with the tiles energised by a beam that
+/// starts out as `init` lit up.
+pub fn render_energised(grid: &Grid<char>, init: Beam) -> Picture {
+    let energised = detect_energised(grid, init);
+    Picture::classify(grid.clone(), |pos, _| {
+        if energised.contains(&Point::from(pos)) {
+            ENERGISED
+        } else {
+            DARK
+        }
+    })
+}
+
+const FRONT: Class = Class::new("front", Colour::rgb(255, 110, 40));
+
+// energised empty tiles show as `#`, as in the puzzle
+fn beam_frame(grid: &Grid<char>, energised: &HashSet<Point>, front: &HashSet<Point>) -> Picture {
+    let mut text = grid.clone();
+    for &pos in energised {
+        if text[pos] == '.' {
+            text[pos] = '#';
+        }
+    }
+    Picture::classify(text, |pos, _| {
+        let pos = Point::from(pos);
+        if front.contains(&pos) {
+            FRONT
+        } else if energised.contains(&pos) {
+            ENERGISED
+        } else {
+            DARK
+        }
+    })
+}
+
+/// Plays the beams that start out as `init` spreading through the
+/// contraption on `screen`, one frame per step.
+pub fn animate_beams(grid: &Grid<char>, init: Beam, screen: &mut dyn Screen) -> ControlFlow<()> {
+    let mut energised = HashSet::new();
+    let mut front = HashSet::new();
+    let mut step = 0;
+    let mut stopped = ControlFlow::Continue(());
+    search::bfs(&Contraption(grid), [init], |&(pos, _), dist| {
+        if dist > step {
+            let caption = format!("step {}, {} tiles energised", step, energised.len());
+            stopped = screen.show(&beam_frame(grid, &energised, &front), &caption);
+            stopped?;
+            (step, front) = (dist, HashSet::new());
+        }
+        energised.insert(pos);
+        front.insert(pos);
+        ControlFlow::Continue(())
+    });
+    stopped?;
+    let caption = format!("done, {} tiles energised", energised.len());
+    screen.show(&beam_frame(grid, &energised, &HashSet::new()), &caption)
+}
+
+// ----------------------------------------------------------------------------
+fn random_contraption(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let mut contraption = Grid::new(size, size, '.');
+    for pos in contraption.positions().collect::<Vec<_>>() {
+        if rng.chance(1, 10) {
+            contraption[pos] = *rng.choose(&['/', '\\', '|', '-']);
+        }
+    }
+    contraption.to_string()
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the contraption into a grid.
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Grid<char>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(Self::DAY, input, r".|-/\")
+    }
+
+    fn part1(lines: &Self::Input) -> usize {
+        let energised = detect_energised(lines, (Point::new(0, 0), Direction::E));
+        energised.len()
+    }
+
+    fn part2(lines: &Self::Input) -> usize {
+        max_energised(lines)
+    }
+
+    fn render(lines: &Self::Input) -> Option<Picture> {
+        Some(render_energised(lines, (Point::new(0, 0), Direction::E)))
+    }
+
+    fn animate(lines: &Self::Input, screen: &mut dyn Screen) -> bool {
+        let _ = animate_beams(lines, (Point::new(0, 0), Direction::E), screen);
+        true
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_contraption(rng, size)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day16);
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day16::parse(".|.\n./x\n").unwrap_err();
+        assert_eq!(err, ParseError::new(16, 2, 3, r"one of `.|-/\`", "`x`"));
+    }
+
+    #[derive(Default)]
+    struct Frames(Vec<(String, String)>);
+
+    impl Screen for Frames {
+        fn show(&mut self, frame: &Picture, caption: &str) -> ControlFlow<()> {
+            self.0.push((caption.to_string(), frame.text().to_string()));
+            ControlFlow::Continue(())
+        }
+    }
+
+    #[test]
+    fn test_animate_beams() {
+        let grid = Day16::parse(Day16::EXAMPLE).unwrap();
+        let mut frames = Frames::default();
+        assert!(Day16::animate(&grid, &mut frames));
+
+        let (caption, first) = &frames.0[0];
+        assert_eq!(caption, "step 0, 1 tiles energised");
+        assert!(first.starts_with("#|...\\....\n|.-.\\....."));
+
+        let (caption, last) = frames.0.last().unwrap();
+        assert_eq!(caption, "done, 46 tiles energised");
+        assert!(last.starts_with("#|###\\....\n|#-.\\#...."));
+    }
+
+    #[test]
+    fn test_render_energised() {
+        let grid = Day16::parse(Day16::EXAMPLE).unwrap();
+        let picture = render_energised(&grid, (Point::new(0, 0), Direction::E));
+        let lit = picture.classes().iter().filter(|(_, c)| **c == ENERGISED);
+        assert_eq!(lit.count(), Day16::part1(&grid));
+        assert_eq!(picture.classes()[(0, 0)], ENERGISED);
+    }
+}
@@ -0,0 +1,351 @@
+//! Day 17: Clumsy Crucible
+//!
+//! A crucible has to get from the top-left block of the city to the
+//! bottom-right one while losing as little heat as possible, but it can only
+//! go so far in a straight line, and an ultra crucible also has to go a
+//! minimum distance before it turns or stops.
+
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use aoc_common::animate::Screen;
+use aoc_common::render::{Class, Colour, Picture};
+use aoc_common::search::{self, Graph, Search};
+use aoc_common::{
+    span, trace, CancellationToken, Cancelled, Direction, Grid, ParseError, Point, Rng, Solution,
+};
+
+fn directions(
+    dir: Direction,
+    min_mom: usize,
+    max_mom: usize,
+    min_momentum: usize,
+    max_momentum: usize,
+) -> Vec<(Direction, usize, usize)> {
+    let mut dirs = vec![];
+
+    if min_mom > 0 {
+        dirs.push((dir, min_mom - 1, max_mom - 1));
+        return dirs;
+    }
+
+    if max_mom > 0 {
+        dirs.push((dir, 0, max_mom - 1));
+    }
+
+    let nmin = if min_momentum > 0 {
+        min_momentum - 1
+    } else {
+        0
+    };
+    let nmax = max_momentum - 1;
+
+    dirs.push((dir.turn_left(), nmin, nmax));
+    dirs.push((dir.turn_right(), nmin, nmax));
+
+    dirs
+}
+
+// position, heading, and how many more blocks the crucible must and may go straight
+type State = (Point, Direction, usize, usize);
+
+struct City<'a> {
+    losses: &'a Grid<usize>,
+    min_momentum: usize,
+    max_momentum: usize,
+}
+
+impl Graph for City<'_> {
+    type State = State;
+
+    fn neighbours(&self, &(pos, dir, min_mom, max_mom): &State) -> Vec<State> {
+        directions(dir, min_mom, max_mom, self.min_momentum, self.max_momentum)
+            .into_iter()
+            .filter_map(|(ndir, nmimom, nmamom)| {
+                self.losses
+                    .step(pos, ndir)
+                    .map(|next| (next, ndir, nmimom, nmamom))
+            })
+            .collect()
+    }
+
+    fn cost(&self, _: &State, &(next, ..): &State) -> usize {
+        self.losses[next]
+    }
+}
+
+// the search stops at the bottom-right block, or when `visit` says so
+fn search_city<V>(
+    losses: &Grid<usize>,
+    min_momentum: usize,
+    max_momentum: usize,
+    mut visit: V,
+) -> Search<State>
+where
+    V: FnMut(&State, usize) -> ControlFlow<()>,
+{
+    let city = City {
+        losses,
+        min_momentum,
+        max_momentum,
+    };
+    let target = Point::new(losses.height() - 1, losses.width() - 1);
+    let start = Point::new(0, 0);
+    let starts = [Direction::E, Direction::S].map(|dir| (start, dir, min_momentum, max_momentum));
+
+    search::dijkstra(&city, starts, |state, dist| {
+        trace!("popped {:?} at heat loss {}", state, dist);
+        visit(state, dist)?;
+        let &(pos, _, min_mom, _) = state;
+        if pos == target && min_mom == 0 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+}
+
+/// The least heat lost on the way across `losses` by a crucible that must
+/// go at least `min_momentum` and at most `max_momentum` blocks in a straight
+/// line. Returns 0 if the bottom-right block cannot be reached.
+pub fn min_heat_loss(
+    losses: &Grid<usize>,
+    min_momentum: usize,
+    max_momentum: usize,
+    token: &CancellationToken,
+) -> Result<usize, Cancelled> {
+    let _span = span!("crucible moving {}..={} blocks", min_momentum, max_momentum);
+    let search = search_city(losses, min_momentum, max_momentum, |_, _| {
+        if token.is_cancelled() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    });
+    token.check()?;
+    Ok(search
+        .stopped_at()
+        .and_then(|state| search.distance(state))
+        .unwrap_or(0))
+}
+
+const PATH: Class = Class::new("path", Colour::rgb(240, 120, 40));
+const BLOCK: Class = Class::new("block", Colour::rgb(225, 225, 225));
+const SETTLED: Class = Class::new("settled", Colour::rgb(150, 190, 230));
+const FRONTIER: Class = Class::new("frontier", Colour::rgb(40, 90, 200));
+
+fn digits(losses: &Grid<usize>) -> Grid<char> {
+    losses.map(|&loss| char::from_digit(loss as u32, 10).unwrap_or('?'))
+}
+
+// the city with the path to where the search stopped marked
+fn path_picture(losses: &Grid<usize>, search: &Search<State>) -> Picture {
+    let path: HashSet<Point> = search
+        .stopped_at()
+        .and_then(|state| search.path(state))
+        .into_iter()
+        .flatten()
+        .map(|(pos, ..)| pos)
+        .collect();
+    Picture::classify(digits(losses), |pos, _| {
+        if path.contains(&Point::from(pos)) {
+            PATH
+        } else {
+            BLOCK
+        }
+    })
+}
+
+/// A picture of the city with the path of least heat loss for a crucible
+/// with the given momentum limits, as in [`min_heat_loss`], marked.
+pub fn render_path(losses: &Grid<usize>, min_momentum: usize, max_momentum: usize) -> Picture {
+    let search = search_city(losses, min_momentum, max_momentum, |_, _| {
+        ControlFlow::Continue(())
+    });
+    path_picture(losses, &search)
+}
+
+// blocks the search has been through show as `.`, those it is at as `*`
+fn frontier_frame(
+    losses: &Grid<usize>,
+    settled: &HashSet<Point>,
+    frontier: &HashSet<Point>,
+) -> Picture {
+    let mut text = digits(losses);
+    let mut classes = Grid::new(text.width(), text.height(), BLOCK);
+    for (blocks, c, class) in [(settled, '.', SETTLED), (frontier, '*', FRONTIER)] {
+        for &pos in blocks {
+            text[pos] = c;
+            classes[pos] = class;
+        }
+    }
+    Picture::new(text, classes)
+}
+
+/// Plays the search for the path of least heat loss, as in
+/// [`min_heat_loss`], on `screen`: one frame per distance the search
+/// reaches, then the path it found.
+pub fn animate_search(
+    losses: &Grid<usize>,
+    min_momentum: usize,
+    max_momentum: usize,
+    screen: &mut dyn Screen,
+) -> ControlFlow<()> {
+    let mut settled = HashSet::new();
+    let mut frontier = HashSet::new();
+    let mut reached = 0;
+    let mut stopped = ControlFlow::Continue(());
+    let search = search_city(losses, min_momentum, max_momentum, |&(pos, ..), dist| {
+        if dist > reached {
+            let caption = format!("heat loss {}", reached);
+            stopped = screen.show(&frontier_frame(losses, &settled, &frontier), &caption);
+            stopped?;
+            settled.extend(frontier.drain());
+            reached = dist;
+        }
+        frontier.insert(pos);
+        ControlFlow::Continue(())
+    });
+    stopped?;
+    let caption = match search.stopped_at().and_then(|state| search.distance(state)) {
+        Some(loss) => format!("done, heat loss {}", loss),
+        None => "done, no way through".to_string(),
+    };
+    screen.show(&path_picture(losses, &search), &caption)
+}
+
+// ----------------------------------------------------------------------------
+// at least 5 blocks a side, so that the ultra crucible can always stop at
+// the end after four or more blocks in a straight line
+fn random_city(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(5);
+    let mut city = Grid::new(size, size, '1');
+    for pos in city.positions().collect::<Vec<_>>() {
+        city[pos] = char::from_digit(rng.range(1..10) as u32, 10).unwrap();
+    }
+    city.to_string()
+}
+
+// ----------------------------------------------------------------------------
+/// The puzzle as a [`Solution`], which parses the city into a grid of the
+/// heat lost in each block.
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const EXAMPLE: &'static str = include_str!("../fixtures/example.txt");
+
+    type Input = Grid<usize>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse_with(Self::DAY, input, "0123456789", |c| {
+            c.to_digit(10).expect("parse only accepts digits") as usize
+        })
+    }
+
+    fn part1(lines: &Self::Input) -> usize {
+        Self::part1_cancellable(lines, &CancellationToken::new())
+            .expect("the token is never cancelled")
+    }
+
+    fn part2(lines: &Self::Input) -> usize {
+        Self::part2_cancellable(lines, &CancellationToken::new())
+            .expect("the token is never cancelled")
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> String {
+        random_city(rng, size)
+    }
+
+    fn render(lines: &Self::Input) -> Option<Picture> {
+        Some(render_path(lines, 0, 3))
+    }
+
+    fn animate(lines: &Self::Input, screen: &mut dyn Screen) -> bool {
+        let _ = animate_search(lines, 0, 3, screen);
+        true
+    }
+
+    fn part1_cancellable(
+        lines: &Self::Input,
+        token: &CancellationToken,
+    ) -> Result<usize, Cancelled> {
+        min_heat_loss(lines, 0, 3, token)
+    }
+
+    fn part2_cancellable(
+        lines: &Self::Input,
+        token: &CancellationToken,
+    ) -> Result<usize, Cancelled> {
+        min_heat_loss(lines, 4, 10, token)
+    }
+}
+
+// ----------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    aoc_common::fixture_tests!(Day17);
+
+    #[test]
+    fn test_parse_errors() {
+        let err = Day17::parse("241\n3a5\n").unwrap_err();
+        assert_eq!(err, ParseError::new(17, 2, 2, "one of `0123456789`", "`a`"));
+    }
+
+    #[test]
+    fn test_cancelled() {
+        let lines = Day17::parse(Day17::EXAMPLE).unwrap();
+        let token = CancellationToken::new();
+        token.cancel();
+        assert_eq!(Day17::part2_cancellable(&lines, &token), Err(Cancelled));
+    }
+
+    #[derive(Default)]
+    struct Frames(Vec<(String, String)>);
+
+    impl Screen for Frames {
+        fn show(&mut self, frame: &Picture, caption: &str) -> ControlFlow<()> {
+            self.0.push((caption.to_string(), frame.text().to_string()));
+            ControlFlow::Continue(())
+        }
+    }
+
+    #[test]
+    fn test_animate_search() {
+        let lines = Day17::parse(Day17::EXAMPLE).unwrap();
+        let mut frames = Frames::default();
+        assert!(Day17::animate(&lines, &mut frames));
+
+        let (caption, first) = &frames.0[0];
+        assert_eq!(caption, "heat loss 0");
+        assert!(first.starts_with("*413432311323\n"));
+        let (caption, second) = &frames.0[1];
+        assert_eq!(caption, "heat loss 3");
+        assert!(second.starts_with(".413432311323\n*215453535623\n"));
+
+        let (caption, last) = frames.0.last().unwrap();
+        assert_eq!(caption, "done, heat loss 102");
+        assert_eq!(last.trim_end(), Day17::EXAMPLE.trim_end());
+    }
+
+    #[test]
+    fn test_render_path() {
+        let lines = Day17::parse(Day17::EXAMPLE).unwrap();
+        let picture = render_path(&lines, 0, 3);
+        assert_eq!(
+            picture.text().to_string().trim_end(),
+            Day17::EXAMPLE.trim_end()
+        );
+        // the start block's heat is not lost, since the crucible starts there
+        let lost: usize = picture
+            .classes()
+            .iter()
+            .filter(|(_, class)| **class == PATH)
+            .map(|(pos, _)| lines[pos])
+            .sum();
+        assert_eq!(lost - lines[(0, 0)], Day17::part1(&lines));
+    }
+}